@@ -1,4 +1,12 @@
 pub mod color;
 pub mod command;
+pub mod headless;
 mod input;
+pub mod keymap;
 pub mod main;
+pub mod menu;
+pub mod options;
+pub mod replay;
+pub mod save;
+mod state;
+pub mod theme;
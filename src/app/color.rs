@@ -1,6 +1,67 @@
-use tui::style::Color;
+use anyhow::{anyhow, Error, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    env,
+    str::FromStr,
+};
+use tui::style::{Color, Modifier, Style};
 
-#[derive(Copy, Clone)]
+pub type Palette = BTreeMap<ColorTheme, Color>;
+
+thread_local! {
+    // Rendering only happens on the main thread.
+    static MODE: Cell<ColorMode> = const { Cell::new(ColorMode::TrueColor) };
+    // Overrides the default colors.
+    static PALETTE: RefCell<Palette> = RefCell::default();
+}
+
+// How many colors the terminal can display.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ColorMode {
+    Ansi16,
+    Ansi256,
+    Monochrome, // Bold and dim text instead of colors
+    TrueColor,
+}
+
+impl ColorMode {
+    // Guess from the environment, following https://no-color.org and the COLORTERM convention.
+    pub fn detect() -> Self {
+        let var = |name| env::var(name).unwrap_or_default();
+        Self::from_env(&var("NO_COLOR"), &var("COLORTERM"), &var("TERM"))
+    }
+
+    fn from_env(no_color: &str, color_term: &str, term: &str) -> Self {
+        if !no_color.is_empty() || term == "dumb" {
+            Self::Monochrome
+        } else if matches!(color_term, "truecolor" | "24bit") {
+            Self::TrueColor
+        } else if term.contains("256color") {
+            Self::Ansi256
+        } else {
+            Self::Ansi16
+        }
+    }
+}
+
+impl FromStr for ColorMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "16" => Ok(Self::Ansi16),
+            "256" => Ok(Self::Ansi256),
+            "none" => Ok(Self::Monochrome),
+            "truecolor" => Ok(Self::TrueColor),
+            _ => Err(anyhow!("expected one of: truecolor, 256, 16, none")),
+        }
+    }
+}
+
+// Variant names are used as keys in theme files.
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
 pub enum ColorTheme {
     Bg,
     BoardBorderFg,
@@ -13,28 +74,50 @@ pub enum ColorTheme {
     AsteroidHighHpSmall,
     AsteroidLowHp,
     AsteroidMidHp,
+    BossArmor,
+    BossCore,
     Bullet,
+    EnemyBullet,
+    EnemyFighter,
+    EnemyStrafer,
     ExplosionA,
     ExplosionB,
     ExplosionC,
     ExplosionD,
     Missile,
+    LevelBanner,
+    PowerUpBlink,
     PowerUpHealth,
     PowerUpMissile,
     Ship,
     ShipShields,
 
     // UI
+    BossHealthCurrent,
+    BossHealthHeader,
+    BossHealthLost,
     DisabledButton,
     GameOver,
+    GameOverSeed,
     HealthCurrent,
     HealthHeader,
     HealthLost,
+    HighScoresHeader,
+    HighScoresNew,
+    HighScoresRow,
+    LevelHeader,
+    LevelNumber,
+    MenuItem,
+    MenuItemSelected,
+    MenuText,
+    MenuTitle,
     MissileButton,
     MissileButtonActive,
     MissilesCurrent,
     MissilesHeader,
     MissilesLost,
+    RewindButton,
+    RewindButtonActive,
     ShieldsButton,
     ShieldsButtonActive,
     ScoreHeader,
@@ -42,6 +125,8 @@ pub enum ColorTheme {
 }
 
 const BLACK: Color = Color::Rgb(21, 21, 21);
+const BLUE: Color = Color::Rgb(51, 102, 204);
+const BLUE_LIGHT: Color = Color::Rgb(153, 204, 255);
 const GREEN: Color = Color::Rgb(102, 175, 51);
 const GREEN_LIGHT: Color = Color::Rgb(153, 255, 153);
 const GREY: Color = Color::Rgb(104, 104, 104);
@@ -54,7 +139,41 @@ const RED: Color = Color::Rgb(204, 25, 25);
 
 impl From<ColorTheme> for Color {
     fn from(color_theme: ColorTheme) -> Self {
-        match color_theme {
+        let color = color_theme.themed_color();
+        match MODE.get() {
+            ColorMode::Ansi16 => nearest_ansi16(color),
+            ColorMode::Ansi256 => nearest_ansi256(color),
+            ColorMode::Monochrome => Color::Reset,
+            ColorMode::TrueColor => color,
+        }
+    }
+}
+
+// The foreground color, or in monochrome, bold for bright colors and dim for dark colors.
+impl From<ColorTheme> for Style {
+    fn from(color_theme: ColorTheme) -> Self {
+        let style = Style::default().fg(Color::from(color_theme));
+        if MODE.get() != ColorMode::Monochrome {
+            return style;
+        }
+        match luminance(color_theme.themed_color()) {
+            Some(luminance) if luminance >= 192 => style.add_modifier(Modifier::BOLD),
+            Some(luminance) if luminance < 96 => style.add_modifier(Modifier::DIM),
+            _ => style,
+        }
+    }
+}
+
+impl ColorTheme {
+    // The color from the active theme, before it's reduced to what the terminal can display.
+    fn themed_color(self) -> Color {
+        PALETTE
+            .with(|palette| palette.borrow().get(&self).copied())
+            .unwrap_or_else(|| self.default_color())
+    }
+
+    fn default_color(self) -> Color {
+        match self {
             ColorTheme::Bg => BLACK,
             ColorTheme::BoardBorderFg => GREY,
             ColorTheme::BoardTitleFg => GREY_MEDIUM_LIGHT,
@@ -66,28 +185,50 @@ impl From<ColorTheme> for Color {
             ColorTheme::AsteroidHighHpSmall => Color::Rgb(88, 77, 66),
             ColorTheme::AsteroidLowHp => Color::Rgb(44, 33, 22),
             ColorTheme::AsteroidMidHp => Color::Rgb(66, 55, 44),
+            ColorTheme::BossArmor => Color::Rgb(102, 102, 128),
+            ColorTheme::BossCore => Color::Rgb(255, 51, 102),
             ColorTheme::Bullet => Color::Rgb(204, 204, 0),
+            ColorTheme::EnemyBullet => Color::Rgb(255, 128, 0),
+            ColorTheme::EnemyFighter => Color::Rgb(204, 102, 0),
+            ColorTheme::EnemyStrafer => Color::Rgb(204, 153, 51),
             ColorTheme::ExplosionA => Color::Rgb(153, 0, 0),
             ColorTheme::ExplosionB => RED,
             ColorTheme::ExplosionC => Color::Rgb(255, 51, 51),
             ColorTheme::ExplosionD => Color::Rgb(255, 104, 104),
             ColorTheme::Missile => PINK,
+            ColorTheme::LevelBanner => GREY_LIGHT,
+            ColorTheme::PowerUpBlink => GREY_LIGHT,
             ColorTheme::PowerUpHealth => RED,
             ColorTheme::PowerUpMissile => PINK,
             ColorTheme::Ship => Color::Rgb(51, 153, 204),
             ColorTheme::ShipShields => GREEN_LIGHT,
 
             // UI
+            ColorTheme::BossHealthCurrent => Color::Rgb(255, 51, 102),
+            ColorTheme::BossHealthHeader => GREY,
+            ColorTheme::BossHealthLost => GREY_DARK,
             ColorTheme::DisabledButton => GREY_DARK,
             ColorTheme::GameOver => Color::Rgb(204, 102, 153),
+            ColorTheme::GameOverSeed => GREY,
             ColorTheme::HealthCurrent => RED,
             ColorTheme::HealthHeader => GREY,
             ColorTheme::HealthLost => GREY,
+            ColorTheme::HighScoresHeader => GREY_LIGHT,
+            ColorTheme::HighScoresNew => Color::Rgb(204, 102, 153),
+            ColorTheme::HighScoresRow => GREY_MEDIUM_LIGHT,
+            ColorTheme::LevelHeader => GREY,
+            ColorTheme::LevelNumber => GREY_LIGHT,
+            ColorTheme::MenuItem => GREY_MEDIUM_LIGHT,
+            ColorTheme::MenuItemSelected => PINK_LIGHT,
+            ColorTheme::MenuText => GREY_MEDIUM_LIGHT,
+            ColorTheme::MenuTitle => GREY_LIGHT,
             ColorTheme::MissileButton => PINK,
             ColorTheme::MissileButtonActive => PINK_LIGHT,
             ColorTheme::MissilesCurrent => PINK,
             ColorTheme::MissilesHeader => GREY,
             ColorTheme::MissilesLost => GREY,
+            ColorTheme::RewindButton => BLUE,
+            ColorTheme::RewindButtonActive => BLUE_LIGHT,
             ColorTheme::ShieldsButton => GREEN,
             ColorTheme::ShieldsButtonActive => GREEN_LIGHT,
             ColorTheme::ScoreHeader => GREY,
@@ -95,3 +236,205 @@ impl From<ColorTheme> for Color {
         }
     }
 }
+
+pub fn set_mode(mode: ColorMode) {
+    MODE.set(mode);
+}
+
+pub fn set_palette(palette: Palette) {
+    PALETTE.with(|current| *current.borrow_mut() = palette);
+}
+
+// The standard xterm colors, in the order of their ANSI codes.
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+// The levels of each channel in the 6x6x6 color cube of the 256 color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).unsigned_abs().pow(2);
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+// In degrees, where red is 0, green is 120, and blue is 240.
+fn hue(r: u8, g: u8, b: u8) -> u16 {
+    let (r, g, b) = (f64::from(r), f64::from(g), f64::from(b));
+    let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+    if max == min {
+        return 0;
+    }
+    let hue = if max == r {
+        60.0 * ((g - b) / (max - min))
+    } else if max == g {
+        60.0 * ((b - r) / (max - min)) + 120.0
+    } else {
+        60.0 * ((r - g) / (max - min)) + 240.0
+    };
+    clamp_to_u16(hue.rem_euclid(360.0), 360) % 360
+}
+
+// Round to the nearest integer from 0 to `max`. Casting a float to an integer saturates, but it
+// would also drop the fraction, and a NaN would become 0.
+fn clamp_to_u16(value: f64, max: u16) -> u16 {
+    if value.is_nan() {
+        return 0;
+    }
+    value.round().clamp(0.0, f64::from(max)) as u16
+}
+
+fn luminance(color: Color) -> Option<u8> {
+    let (r, g, b) = rgb(color)?;
+    let luminance = (299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b)) / 1000;
+    Some(u8::try_from(luminance).unwrap_or(u8::MAX))
+}
+
+// Distance alone maps pale colors to gray, so match by hue, then by how light the color is.
+fn nearest_ansi16(color: Color) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+    let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+    if max - min < 48 {
+        return match (u16::from(max) + u16::from(min)) / 2 {
+            0..=47 => Color::Black,
+            48..=143 => Color::DarkGray,
+            144..=215 => Color::Gray,
+            _ => Color::White,
+        };
+    }
+    let hues = [
+        (Color::Red, Color::LightRed),
+        (Color::Yellow, Color::LightYellow),
+        (Color::Green, Color::LightGreen),
+        (Color::Cyan, Color::LightCyan),
+        (Color::Blue, Color::LightBlue),
+        (Color::Magenta, Color::LightMagenta),
+    ];
+    let sector = (hue(r, g, b) + 30) % 360 / 60;
+    let (dark, light) = hues[usize::from(sector)];
+    if max > 230 {
+        light
+    } else {
+        dark
+    }
+}
+
+fn nearest_ansi256(color: Color) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+    // The index of the cube's nearest level.
+    let levels = u8::try_from(CUBE_LEVELS.len()).unwrap();
+    let level = |channel: u8| {
+        (0..levels)
+            .min_by_key(|&i: &u8| CUBE_LEVELS[usize::from(i)].abs_diff(channel))
+            .unwrap_or_default()
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube_level = |i: u8| CUBE_LEVELS[usize::from(i)];
+    let cube = (cube_level(ri), cube_level(gi), cube_level(bi));
+    // The grayscale ramp from 8 to 238 is finer than the cube's grays.
+    let average = (u16::from(r) + u16::from(g) + u16::from(b)) / 3;
+    let gray_index = u8::try_from((average.saturating_sub(3) / 10).min(23)).unwrap();
+    let gray_level = 8 + 10 * gray_index;
+    let gray = (gray_level, gray_level, gray_level);
+    if distance(gray, (r, g, b)) < distance(cube, (r, g, b)) {
+        Color::Indexed(232 + gray_index)
+    } else {
+        Color::Indexed(16 + 36 * ri + 6 * gi + bi)
+    }
+}
+
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Reset => None,
+        _ => ANSI16
+            .iter()
+            .find(|(ansi, _)| *ansi == color)
+            .map(|(_, rgb)| *rgb),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_env_detects_the_color_mode() {
+        assert_eq!(
+            ColorMode::from_env("1", "truecolor", "xterm-256color"),
+            ColorMode::Monochrome
+        );
+        assert_eq!(
+            ColorMode::from_env("", "truecolor", "xterm-256color"),
+            ColorMode::TrueColor
+        );
+        assert_eq!(
+            ColorMode::from_env("", "", "xterm-256color"),
+            ColorMode::Ansi256
+        );
+        assert_eq!(ColorMode::from_env("", "", "xterm"), ColorMode::Ansi16);
+        assert_eq!(ColorMode::from_env("", "", "dumb"), ColorMode::Monochrome);
+    }
+
+    #[test]
+    fn nearest_ansi16_matches_the_closest_color() {
+        assert_eq!(nearest_ansi16(Color::Rgb(204, 25, 25)), Color::Red);
+        assert_eq!(nearest_ansi16(Color::Rgb(21, 21, 21)), Color::Black);
+        assert_eq!(nearest_ansi16(Color::Rgb(153, 255, 153)), Color::LightGreen);
+        assert_eq!(nearest_ansi16(Color::Rgb(51, 153, 204)), Color::Cyan);
+        assert_eq!(nearest_ansi16(Color::Rgb(104, 104, 104)), Color::DarkGray);
+        assert_eq!(nearest_ansi16(Color::Yellow), Color::Yellow);
+    }
+
+    #[test]
+    fn nearest_ansi256_matches_the_closest_color() {
+        assert_eq!(nearest_ansi256(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+        assert_eq!(
+            nearest_ansi256(Color::Rgb(51, 153, 204)),
+            Color::Indexed(68)
+        );
+        assert_eq!(
+            nearest_ansi256(Color::Rgb(104, 104, 104)),
+            Color::Indexed(242)
+        );
+    }
+
+    #[test]
+    fn hue_and_luminance_stay_in_range() {
+        assert_eq!(hue(0, 0, 255), 240);
+        assert_eq!(hue(255, 0, 1), 0); // 359.8 degrees
+        assert_eq!(hue(7, 7, 7), 0);
+        assert_eq!(luminance(Color::Rgb(255, 255, 255)), Some(255));
+        assert_eq!(luminance(Color::Reset), None);
+    }
+
+    #[test]
+    fn monochrome_uses_modifiers() {
+        set_mode(ColorMode::Monochrome);
+
+        assert_eq!(Color::from(ColorTheme::Ship), Color::Reset);
+        let bright = Style::from(ColorTheme::MenuItemSelected);
+        assert!(bright.add_modifier.contains(Modifier::BOLD));
+        let dark = Style::from(ColorTheme::DisabledButton);
+        assert!(dark.add_modifier.contains(Modifier::DIM));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    game::game_item::GameItemKind,
+    game::{actors::asteroid::AsteroidSize, game_item::GameItemKind, high_scores::Initials},
     view::{
         coordinates::{Coordinates, Movement},
         viewport::Viewport,
@@ -15,16 +15,19 @@ pub trait CommandHandler {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Command {
     Continue,
-    GameOver, // Display a prompt to quit or restart
+    GameOver, // The ship was destroyed
+    LoadGame, // Continue the saved game
     Quit,     // Exit the application
     Restart,
 
     // Actors
-    Collide(GameItemKind),
+    Collide(GameItemKind, usize), // The other actor's kind, and which of this actor's hitboxes it hit
+    AddAsteroid(Coordinates, AsteroidSize, i16), // Drift
     AddBullet(Coordinates),
+    AddEnemyBullet(Coordinates, i16), // Horizontal velocity, in subcells per tick
     AddExplosion(Coordinates),
     AddMissile(Coordinates),
     MoveOffset(Movement),
@@ -32,6 +35,7 @@ pub enum Command {
 
     // Button commands
     PressMissileButton,
+    PressRewindButton,
     PressShieldsButton,
 
     // Ship commands
@@ -39,11 +43,31 @@ pub enum Command {
     FireMissile,
     FireShields,
 
+    // World commands
+    BossDefeated,
+    Pause, // Pause if not already paused, such as when the terminal loses focus
+    Rewind,
+    SaveHighScore(Initials),
+    TogglePause,
+
+    // Menu commands
+    MenuBack,
+    MenuDown,
+    MenuSelect,
+    MenuUp,
+
+    // High score commands
+    DeleteInitial,
+    EnterInitial(char),
+    SubmitInitials,
+
     // UI
     IncreaseHealth(u8),
     IncreaseMissiles(u8),
     IncreaseScore(u32),
+    UpdateBossHealth(u8, u8),
     UpdateHealth(u8, u8),
+    UpdateLevel(u8),
     UpdateMissiles(u8, u8),
 
     // Viewports
@@ -52,25 +76,158 @@ pub enum Command {
     UiViewportInitializedOrChanged(Viewport),
 }
 
-impl From<KeyEvent> for Command {
-    fn from(event: KeyEvent) -> Self {
-        let KeyEvent { code, modifiers } = event;
-        if let (KeyCode::Char('c'), KeyModifiers::CONTROL) = (code, modifiers) {
-            return Self::Quit;
+// The type of a Command, without its data, e.g. to subscribe to every `UpdateHealth` command.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CommandKind {
+    Continue,
+    GameOver,
+    LoadGame,
+    Quit,
+    Restart,
+
+    // Actors
+    Collide,
+    AddAsteroid,
+    AddBullet,
+    AddEnemyBullet,
+    AddExplosion,
+    AddMissile,
+    MoveOffset,
+    MoveShip,
+
+    // Button commands
+    PressMissileButton,
+    PressRewindButton,
+    PressShieldsButton,
+
+    // Ship commands
+    FireGuns,
+    FireMissile,
+    FireShields,
+
+    // World commands
+    BossDefeated,
+    Pause,
+    Rewind,
+    SaveHighScore,
+    TogglePause,
+
+    // Menu commands
+    MenuBack,
+    MenuDown,
+    MenuSelect,
+    MenuUp,
+
+    // High score commands
+    DeleteInitial,
+    EnterInitial,
+    SubmitInitials,
+
+    // UI
+    IncreaseHealth,
+    IncreaseMissiles,
+    IncreaseScore,
+    UpdateBossHealth,
+    UpdateHealth,
+    UpdateLevel,
+    UpdateMissiles,
+
+    // Viewports
+    ActorsViewportChanged,
+    ActorsViewportInitialized,
+    UiViewportInitializedOrChanged,
+}
+
+impl Command {
+    pub fn kind(&self) -> CommandKind {
+        match self {
+            Self::Continue => CommandKind::Continue,
+            Self::GameOver => CommandKind::GameOver,
+            Self::LoadGame => CommandKind::LoadGame,
+            Self::Quit => CommandKind::Quit,
+            Self::Restart => CommandKind::Restart,
+            Self::Collide(..) => CommandKind::Collide,
+            Self::AddAsteroid(..) => CommandKind::AddAsteroid,
+            Self::AddBullet(..) => CommandKind::AddBullet,
+            Self::AddEnemyBullet(..) => CommandKind::AddEnemyBullet,
+            Self::AddExplosion(..) => CommandKind::AddExplosion,
+            Self::AddMissile(..) => CommandKind::AddMissile,
+            Self::MoveOffset(..) => CommandKind::MoveOffset,
+            Self::MoveShip(..) => CommandKind::MoveShip,
+            Self::PressMissileButton => CommandKind::PressMissileButton,
+            Self::PressRewindButton => CommandKind::PressRewindButton,
+            Self::PressShieldsButton => CommandKind::PressShieldsButton,
+            Self::FireGuns => CommandKind::FireGuns,
+            Self::FireMissile => CommandKind::FireMissile,
+            Self::FireShields => CommandKind::FireShields,
+            Self::BossDefeated => CommandKind::BossDefeated,
+            Self::Pause => CommandKind::Pause,
+            Self::Rewind => CommandKind::Rewind,
+            Self::SaveHighScore(..) => CommandKind::SaveHighScore,
+            Self::TogglePause => CommandKind::TogglePause,
+            Self::MenuBack => CommandKind::MenuBack,
+            Self::MenuDown => CommandKind::MenuDown,
+            Self::MenuSelect => CommandKind::MenuSelect,
+            Self::MenuUp => CommandKind::MenuUp,
+            Self::DeleteInitial => CommandKind::DeleteInitial,
+            Self::EnterInitial(..) => CommandKind::EnterInitial,
+            Self::SubmitInitials => CommandKind::SubmitInitials,
+            Self::IncreaseHealth(..) => CommandKind::IncreaseHealth,
+            Self::IncreaseMissiles(..) => CommandKind::IncreaseMissiles,
+            Self::IncreaseScore(..) => CommandKind::IncreaseScore,
+            Self::UpdateBossHealth(..) => CommandKind::UpdateBossHealth,
+            Self::UpdateHealth(..) => CommandKind::UpdateHealth,
+            Self::UpdateLevel(..) => CommandKind::UpdateLevel,
+            Self::UpdateMissiles(..) => CommandKind::UpdateMissiles,
+            Self::ActorsViewportChanged(..) => CommandKind::ActorsViewportChanged,
+            Self::ActorsViewportInitialized(..) => CommandKind::ActorsViewportInitialized,
+            Self::UiViewportInitializedOrChanged(..) => CommandKind::UiViewportInitializedOrChanged,
         }
-        match code {
-            KeyCode::Down | KeyCode::Char('s') => Self::MoveShip((0, -1)),
-            KeyCode::Up | KeyCode::Char('w') => Self::MoveShip((0, 1)),
-            KeyCode::Right | KeyCode::Char('d') => Self::MoveShip((1, 0)),
-            KeyCode::Left | KeyCode::Char('a') => Self::MoveShip((-1, 0)),
-
-            KeyCode::Char(' ') => Self::FireGuns,
-            KeyCode::Char('j') => Self::PressMissileButton,
-            KeyCode::Char('k') => Self::PressShieldsButton,
-
-            KeyCode::Char('q') => Self::Quit,
-            KeyCode::Char('r') => Self::Restart,
-            _ => Command::Continue,
+    }
+}
+
+// Keys are typed into the high score table, rather than controlling the ship, while the player is
+// entering their initials.
+pub fn initials_command(event: KeyEvent) -> Command {
+    let KeyEvent {
+        code, modifiers, ..
+    } = event;
+    match (code, modifiers) {
+        (KeyCode::Char('c'), KeyModifiers::CONTROL) => Command::Quit,
+        (KeyCode::Char(char), _) if char.is_ascii_alphanumeric() => {
+            Command::EnterInitial(char.to_ascii_uppercase())
+        }
+        (KeyCode::Backspace, _) => Command::DeleteInitial,
+        (KeyCode::Enter, _) => Command::SubmitInitials,
+        _ => Command::Continue,
+    }
+}
+
+// The keyboard can only stop a replay.
+pub fn replay_command(event: KeyEvent) -> Command {
+    let KeyEvent {
+        code, modifiers, ..
+    } = event;
+    match (code, modifiers) {
+        (KeyCode::Char('c'), KeyModifiers::CONTROL) | (KeyCode::Esc | KeyCode::Char('q'), _) => {
+            Command::Quit
         }
+        _ => Command::Continue,
+    }
+}
+
+// Keys navigate the menu while the game isn't being played.
+pub fn menu_command(event: KeyEvent) -> Command {
+    let KeyEvent {
+        code, modifiers, ..
+    } = event;
+    match (code, modifiers) {
+        (KeyCode::Char('c'), KeyModifiers::CONTROL) => Command::Quit,
+        (KeyCode::Up | KeyCode::Char('w' | 'k'), _) => Command::MenuUp,
+        (KeyCode::Down | KeyCode::Char('s' | 'j'), _) => Command::MenuDown,
+        (KeyCode::Enter | KeyCode::Char(' '), _) => Command::MenuSelect,
+        (KeyCode::Backspace | KeyCode::Esc, _) => Command::MenuBack,
+        (KeyCode::Char('p'), _) => Command::TogglePause,
+        _ => Command::Continue,
     }
 }
@@ -0,0 +1,195 @@
+use super::{command::Command, keymap::Keymap};
+use crate::{
+    clock::ticker::Ticker,
+    game::{
+        game_item::GameItemKind,
+        high_scores::HighScores,
+        spawner::levels::{builtin_levels, load_levels},
+        world::{World, DEFAULT_REWIND_SECONDS},
+    },
+    view::render::{broadcast_viewports, render},
+};
+use anyhow::Result;
+use std::{collections::BTreeMap, path::Path, rc::Rc, time::Duration};
+use tui::{backend::TestBackend, buffer::Buffer, layout::Rect, Terminal};
+
+const HEIGHT: u16 = 40;
+const WIDTH: u16 = 100;
+
+// A command to send to the World after the given tick, where the first tick is 1.
+pub type ScriptedCommand = (u64, Command);
+
+// Runs the game as fast as possible, without a terminal or a clock, e.g. for integration tests or
+// to simulate many games when balancing the levels. Frames are only drawn if rendering is enabled.
+pub struct Headless {
+    terminal: Option<Terminal<TestBackend>>,
+    ticker: Ticker,
+    world: World,
+}
+
+// The state of the World after a headless run.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Report {
+    pub actors: BTreeMap<GameItemKind, usize>,
+    pub game_over: bool,
+    pub level: u8,
+    pub score: u32,
+    pub ship_health: u8,
+    pub ticks: u64,
+}
+
+impl Headless {
+    // Load levels from the `*.toml` files in `levels`, or use the built-in levels if it's `None`.
+    pub fn new(seed: u64, levels: Option<&Path>) -> Result<Self> {
+        let levels = match levels {
+            Some(directory) => load_levels(directory)?,
+            None => builtin_levels(),
+        };
+        Ok(Self {
+            terminal: None,
+            ticker: Ticker::new(Duration::ZERO), // Ticked manually
+            world: World::new(
+                Some(seed),
+                levels.into(),
+                HighScores::default(), // Not persisted
+                Rc::new(Keymap::default()),
+                DEFAULT_REWIND_SECONDS,
+            ),
+        })
+    }
+
+    // Draw each frame into an in-memory buffer.
+    pub fn with_rendering(mut self) -> Result<Self> {
+        self.terminal = Some(Terminal::new(TestBackend::new(WIDTH, HEIGHT))?);
+        Ok(self)
+    }
+
+    // The last frame that was drawn, if rendering is enabled.
+    pub fn buffer(&self) -> Option<&Buffer> {
+        self.terminal
+            .as_ref()
+            .map(|terminal| terminal.backend().buffer())
+    }
+
+    // Start a new game, and run it until the ship is destroyed or `max_ticks` have elapsed.
+    pub fn run(&mut self, max_ticks: u64, script: &[ScriptedCommand]) -> Result<Report> {
+        self.ticker.restart();
+        self.world.broadcast_commands(&[Command::Restart]);
+
+        while self.ticker.number() < max_ticks && !self.world.game_over() {
+            self.ticker.tick();
+            self.world.handle_tick(&self.ticker);
+
+            // The player's input is ignored while time is being rewound, as in the App.
+            let tick = self.ticker.number();
+            let commands: Vec<Command> = if self.world.rewinding() {
+                Vec::new()
+            } else {
+                script
+                    .iter()
+                    .filter(|(at, _)| *at == tick)
+                    .map(|(_, command)| *command)
+                    .collect()
+            };
+            self.world.broadcast_commands(&commands);
+            match &mut self.terminal {
+                Some(terminal) => render(terminal, &mut self.world, None)?,
+                None => {
+                    broadcast_viewports(&mut self.world, Rect::new(0, 0, WIDTH, HEIGHT));
+                }
+            }
+            self.world.broadcast_collisions();
+        }
+        Ok(self.report())
+    }
+
+    fn report(&self) -> Report {
+        let mut actors = BTreeMap::new();
+        for actor in self.world.actors.iter().filter(|actor| !actor.deleted()) {
+            *actors.entry(actor.kind()).or_default() += 1;
+        }
+        Report {
+            actors,
+            game_over: self.world.game_over(),
+            level: self.world.level(),
+            score: self.world.score(),
+            ship_health: self.world.ship_health(),
+            ticks: self.ticker.number(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_reports_the_state_of_the_world() {
+        let mut headless = Headless::new(0, None).unwrap();
+
+        let report = headless.run(20, &[]).unwrap();
+
+        assert_eq!(report.ticks, 20);
+        assert!(!report.game_over);
+        assert!(report.ship_health > 0);
+        assert_eq!(report.actors.get(&GameItemKind::Ship), Some(&1));
+    }
+
+    #[test]
+    fn run_sends_the_scripted_commands() {
+        let mut headless = Headless::new(0, None).unwrap();
+
+        let report = headless.run(2, &[(2, Command::FireGuns)]).unwrap();
+
+        assert_eq!(report.actors.get(&GameItemKind::Bullet), Some(&1));
+    }
+
+    #[test]
+    fn every_command_in_a_long_game_is_handled() {
+        let script: Vec<ScriptedCommand> = (100..2000)
+            .step_by(5)
+            .flat_map(|tick| {
+                [
+                    (tick, Command::FireGuns),
+                    (tick, Command::MoveShip((1, 0))),
+                    (tick + 2, Command::PressMissileButton),
+                    (tick + 3, Command::PressShieldsButton),
+                    (tick + 4, Command::PressRewindButton),
+                ]
+            })
+            .collect();
+        let mut headless = Headless::new(3, None).unwrap();
+
+        headless.run(2000, &script).unwrap();
+
+        let report = headless.world.diagnostics().report();
+        assert!(report.is_empty(), "{report:?}");
+    }
+
+    #[test]
+    fn with_rendering_draws_the_world() {
+        let mut headless = Headless::new(0, None).unwrap().with_rendering().unwrap();
+
+        headless.run(5, &[]).unwrap();
+
+        let buffer = headless.buffer().unwrap();
+        let text: String = buffer
+            .content()
+            .iter()
+            .map(|cell| cell.symbol.as_str())
+            .collect();
+        assert!(text.contains("Score"), "{text}");
+    }
+
+    #[test]
+    fn run_is_deterministic() {
+        let script = [(5, Command::FireGuns), (10, Command::FireMissile)];
+        let mut first = Headless::new(7, None).unwrap();
+        let mut second = Headless::new(7, None).unwrap();
+
+        assert_eq!(
+            first.run(200, &script).unwrap(),
+            second.run(200, &script).unwrap()
+        );
+    }
+}
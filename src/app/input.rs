@@ -1,32 +1,60 @@
-use super::command::Command;
+use super::{
+    command::{initials_command, menu_command, replay_command, Command},
+    keymap::Keymap,
+};
 use crossterm::event::{read, Event};
 use std::{
     sync::mpsc::{Receiver, Sender},
     thread,
 };
 
-pub fn receive_input_commands(rx: &Receiver<Command>) -> Vec<Command> {
+// The meaning of keys depends on what the player is doing.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InputMode {
+    Initials,
+    Menu,
+    Playing,
+    Replay,
+}
+
+// Events are converted to commands on the main thread, because their meaning depends on the
+// `InputMode`.
+pub fn receive_input_commands(
+    rx: &Receiver<Event>,
+    mode: InputMode,
+    keymap: &Keymap,
+    auto_pause: bool,
+) -> Vec<Command> {
     let mut commands = Vec::new();
     loop {
-        let command = rx.try_recv();
-        if command.is_err() {
+        let event = rx.try_recv();
+        if event.is_err() {
             break;
         }
-        let command = command.expect("Can receive a command from the input thread");
+        let command = match event.expect("Can receive an event from the input thread") {
+            Event::Key(key) => match mode {
+                InputMode::Initials => initials_command(key),
+                InputMode::Menu => menu_command(key),
+                InputMode::Playing => keymap.command(key),
+                InputMode::Replay => replay_command(key),
+            },
+            Event::FocusLost if auto_pause => Command::Pause,
+            _ => Command::Continue,
+        };
+        if let Command::Continue = command {
+            continue;
+        }
         commands.push(command);
     }
     commands
 }
 
-pub fn send_input_commands(tx: Sender<Command>) {
+pub fn send_input_commands(tx: Sender<Event>) {
     thread::spawn(move || loop {
         // Blocking read
-        if let Event::Key(key) = read().expect("Can read key events") {
-            let command = Command::from(key);
-            if let Command::Continue = command {
-                continue;
-            }
-            tx.send(command).unwrap();
+        let event = read().expect("Can read events");
+        if let Event::Key(_) | Event::FocusLost = event {
+            tx.send(event).unwrap();
         }
     });
 }
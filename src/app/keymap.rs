@@ -0,0 +1,446 @@
+use super::command::Command;
+use anyhow::{anyhow, Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    env, fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+static FILE_NAME: &str = "keymap.toml";
+static DIRECTORY_NAME: &str = "space_time_rewind";
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    FireGuns,
+    Missile,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    Pause,
+    Rewind,
+    Shields,
+}
+
+impl Action {
+    const ALL: [Self; 9] = [
+        Self::FireGuns,
+        Self::Missile,
+        Self::MoveDown,
+        Self::MoveLeft,
+        Self::MoveRight,
+        Self::MoveUp,
+        Self::Pause,
+        Self::Rewind,
+        Self::Shields,
+    ];
+
+    // As in the keymap file.
+    fn name(self) -> &'static str {
+        match self {
+            Self::FireGuns => "fire_guns",
+            Self::Missile => "missile",
+            Self::MoveDown => "move_down",
+            Self::MoveLeft => "move_left",
+            Self::MoveRight => "move_right",
+            Self::MoveUp => "move_up",
+            Self::Pause => "pause",
+            Self::Rewind => "rewind",
+            Self::Shields => "shields",
+        }
+    }
+
+    // The action that sends `command`, e.g. for recording the player's input.
+    pub fn from_command(command: Command) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.command() == command)
+    }
+
+    pub fn command(self) -> Command {
+        match self {
+            Self::FireGuns => Command::FireGuns,
+            Self::Missile => Command::PressMissileButton,
+            Self::MoveDown => Command::MoveShip((0, -1)),
+            Self::MoveLeft => Command::MoveShip((-1, 0)),
+            Self::MoveRight => Command::MoveShip((1, 0)),
+            Self::MoveUp => Command::MoveShip((0, 1)),
+            Self::Pause => Command::TogglePause,
+            Self::Rewind => Command::PressRewindButton,
+            Self::Shields => Command::PressShieldsButton,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Preset {
+    Arrows,
+    Vim,
+    #[default]
+    Wasd,
+}
+
+impl Preset {
+    // The keys that are bound to the action, unless they're replaced in the keymap file.
+    fn keys(self, action: Action) -> &'static [&'static str] {
+        match (self, action) {
+            (_, Action::FireGuns) => &["Space"],
+            (_, Action::Pause) => &["p", "Esc"],
+
+            (Self::Arrows, Action::Missile) => &["z"],
+            (Self::Arrows, Action::MoveDown) => &["Down"],
+            (Self::Arrows, Action::MoveLeft) => &["Left"],
+            (Self::Arrows, Action::MoveRight) => &["Right"],
+            (Self::Arrows, Action::MoveUp) => &["Up"],
+            (Self::Arrows, Action::Rewind) => &["c"],
+            (Self::Arrows, Action::Shields) => &["x"],
+
+            (Self::Vim, Action::Missile) => &["a"],
+            (Self::Vim, Action::MoveDown) => &["j"],
+            (Self::Vim, Action::MoveLeft) => &["h"],
+            (Self::Vim, Action::MoveRight) => &["l"],
+            (Self::Vim, Action::MoveUp) => &["k"],
+            (Self::Vim, Action::Rewind) => &["d"],
+            (Self::Vim, Action::Shields) => &["s"],
+
+            (Self::Wasd, Action::Missile) => &["j"],
+            (Self::Wasd, Action::MoveDown) => &["s", "Down"],
+            (Self::Wasd, Action::MoveLeft) => &["a", "Left"],
+            (Self::Wasd, Action::MoveRight) => &["d", "Right"],
+            (Self::Wasd, Action::MoveUp) => &["w", "Up"],
+            (Self::Wasd, Action::Rewind) => &["l"],
+            (Self::Wasd, Action::Shields) => &["k"],
+        }
+    }
+}
+
+// A key, including any modifiers other than Shift, which is implied by the case of characters.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        let mut modifiers = event.modifiers;
+        if let KeyCode::Char(_) = event.code {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self {
+            code: event.code,
+            modifiers,
+        }
+    }
+}
+
+impl FromStr for Key {
+    type Err = anyhow::Error;
+
+    // E.g. "w", "Space", "Up", "F1", "Ctrl+x", or "Alt+Shift+Left". The "+" key is "Plus".
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts: Vec<&str> = s.split('+').collect();
+        let name = parts.pop().unwrap_or_default();
+        let mut modifiers = KeyModifiers::NONE;
+        for part in parts {
+            modifiers |= match part.to_lowercase().as_str() {
+                "alt" => KeyModifiers::ALT,
+                "ctrl" => KeyModifiers::CONTROL,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(anyhow!("invalid modifier {part:?} in key: {s:?}")),
+            };
+        }
+        let code = match name.to_lowercase().as_str() {
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "down" => KeyCode::Down,
+            "end" => KeyCode::End,
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "home" => KeyCode::Home,
+            "insert" => KeyCode::Insert,
+            "left" => KeyCode::Left,
+            "pagedown" => KeyCode::PageDown,
+            "pageup" => KeyCode::PageUp,
+            "plus" => KeyCode::Char('+'),
+            "right" => KeyCode::Right,
+            "space" => KeyCode::Char(' '),
+            "tab" => KeyCode::Tab,
+            "up" => KeyCode::Up,
+            lowercase => {
+                let mut chars = name.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(char), None) => KeyCode::Char(char),
+                    _ => match lowercase.strip_prefix('f').map(str::parse) {
+                        Some(Ok(number @ 1..=12)) => KeyCode::F(number),
+                        _ => return Err(anyhow!("invalid key: {s:?}")),
+                    },
+                }
+            }
+        };
+        // Shift is implied by the case of characters.
+        let code = match code {
+            KeyCode::Char(char) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(char.to_ascii_uppercase())
+            }
+            code => code,
+        };
+        Ok(Self { code, modifiers })
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char('+') => f.write_str("Plus"),
+            KeyCode::Char(char) => write!(f, "{char}"),
+            KeyCode::F(number) => write!(f, "F{number}"),
+            code => write!(f, "{code:?}"),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct KeymapConfig {
+    #[serde(default)]
+    bindings: BTreeMap<Action, Vec<String>>, // Replace the preset's keys for these actions
+    #[serde(default)]
+    preset: Preset,
+}
+
+// Maps keys to the commands that control the ship while playing. Menus and initials always use the
+// same keys, and Ctrl-C always quits.
+#[derive(Clone, Debug)]
+pub struct Keymap {
+    bindings: BTreeMap<Action, Vec<Key>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(&KeymapConfig::default()).expect("The default keymap is valid")
+    }
+}
+
+impl Keymap {
+    fn new(config: &KeymapConfig) -> Result<Self> {
+        let mut bindings = BTreeMap::new();
+        for action in Action::ALL {
+            let names: Vec<&str> = match config.bindings.get(&action) {
+                Some(names) => names.iter().map(String::as_str).collect(),
+                None => config.preset.keys(action).to_vec(),
+            };
+            let keys = names
+                .iter()
+                .map(|name| name.parse())
+                .collect::<Result<Vec<Key>>>()?;
+            if keys.is_empty() {
+                return Err(anyhow!(
+                    "`{}` must be bound to at least one key",
+                    action.name()
+                ));
+            }
+            bindings.insert(action, keys);
+        }
+        let keymap = Self { bindings };
+        keymap.validate()?;
+        Ok(keymap)
+    }
+
+    pub fn command(&self, event: KeyEvent) -> Command {
+        let key = Key::from(event);
+        if key == quit_key() {
+            return Command::Quit;
+        }
+        self.bindings
+            .iter()
+            .find(|(_, keys)| keys.contains(&key))
+            .map_or(Command::Continue, |(action, _)| action.command())
+    }
+
+    // The first key that's bound to the action, for display on buttons.
+    pub fn label(&self, action: Action) -> String {
+        self.bindings[&action][0].to_string()
+    }
+
+    fn validate(&self) -> Result<()> {
+        let mut seen: Vec<(Key, Action)> = Vec::new();
+        for (action, keys) in &self.bindings {
+            for key in keys {
+                if *key == quit_key() {
+                    return Err(anyhow!("{key} is reserved for quitting"));
+                }
+                if let Some((_, other)) = seen.iter().find(|(other_key, _)| other_key == key) {
+                    return Err(anyhow!(
+                        "{key} is bound to both `{}` and `{}`",
+                        other.name(),
+                        action.name()
+                    ));
+                }
+                seen.push((*key, *action));
+            }
+        }
+        Ok(())
+    }
+}
+
+// `$XDG_CONFIG_HOME/space_time_rewind/keymap.toml`, which defaults to `~/.config/...`.
+pub fn default_path() -> Option<PathBuf> {
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(config_home.join(DIRECTORY_NAME).join(FILE_NAME))
+}
+
+// Load the keymap from a file, or use the default keymap if `required` is false and the file
+// doesn't exist.
+pub fn load_keymap(path: &Path, required: bool) -> Result<Keymap> {
+    if !required && !path.exists() {
+        return Ok(Keymap::default());
+    }
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("cannot read keymap file: {}", path.display()))?;
+    parse(&text).with_context(|| format!("invalid keymap file: {}", path.display()))
+}
+
+// Load the keymap from the default path, which isn't required to be valid: an invalid file is
+// recorded in `errors`, and the default keymap is used instead, so that the game can still start.
+pub fn load_default_keymap(path: &Path, errors: &mut Vec<String>) -> Keymap {
+    load_keymap(path, false).unwrap_or_else(|error| {
+        errors.push(format!("{error:#}"));
+        Keymap::default()
+    })
+}
+
+fn parse(text: &str) -> Result<Keymap> {
+    let config: KeymapConfig = toml::from_str(text)?;
+    Keymap::new(&config)
+}
+
+fn quit_key() -> Key {
+    Key {
+        code: KeyCode::Char('c'),
+        modifiers: KeyModifiers::CONTROL,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_event(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn presets_are_valid() {
+        for preset in [Preset::Arrows, Preset::Vim, Preset::Wasd] {
+            let config = KeymapConfig {
+                preset,
+                ..KeymapConfig::default()
+            };
+            assert!(Keymap::new(&config).is_ok(), "{preset:?}");
+        }
+    }
+
+    #[test]
+    fn parse_keys_with_modifiers() {
+        let key: Key = "Ctrl+Shift+x".parse().unwrap();
+
+        assert_eq!(
+            key,
+            Key::from(key_event(
+                KeyCode::Char('X'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            ))
+        );
+        assert_eq!(key.to_string(), "Ctrl+X");
+        assert_eq!("f5".parse::<Key>().unwrap().to_string(), "F5");
+        assert!("Hyper+x".parse::<Key>().is_err());
+        assert!("Nope".parse::<Key>().is_err());
+    }
+
+    #[test]
+    fn bindings_replace_the_presets_keys() {
+        let keymap = parse(
+            r#"
+            preset = "vim"
+            [bindings]
+            fire_guns = ["f", "Enter"]
+            "#,
+        )
+        .unwrap();
+
+        let command = keymap.command(key_event(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(command, Command::FireGuns));
+        let command = keymap.command(key_event(KeyCode::Char(' '), KeyModifiers::NONE));
+        assert!(matches!(command, Command::Continue));
+        let command = keymap.command(key_event(KeyCode::Char('h'), KeyModifiers::NONE));
+        assert!(matches!(command, Command::MoveShip((-1, 0))));
+        assert_eq!(keymap.label(Action::FireGuns), "f");
+    }
+
+    #[test]
+    fn conflicting_bindings_are_invalid() {
+        let error = parse("[bindings]\nfire_guns = [\"j\"]").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "j is bound to both `fire_guns` and `missile`"
+        );
+        assert!(parse("[bindings]\npause = [\"Ctrl+c\"]").is_err());
+        assert!(parse("[bindings]\npause = []").is_err());
+        assert!(parse("[bindings]\njump = [\"x\"]").is_err());
+    }
+
+    #[test]
+    fn load_keymap_falls_back_to_the_default_when_the_file_is_missing() {
+        let path = env::temp_dir().join(format!(
+            "space_time_rewind_missing_keymap_{}.toml",
+            std::process::id()
+        ));
+
+        assert!(load_keymap(&path, false).is_ok());
+        assert!(load_keymap(&path, true).is_err());
+    }
+
+    #[test]
+    fn load_default_keymap_records_an_invalid_file() {
+        let path = env::temp_dir().join(format!(
+            "space_time_rewind_invalid_keymap_{}.toml",
+            std::process::id()
+        ));
+        fs::write(&path, "[bindings]\nfire_guns = [\"j\"]").unwrap();
+        let mut errors = Vec::new();
+
+        let keymap = load_default_keymap(&path, &mut errors);
+        fs::remove_file(&path).unwrap();
+
+        let command = keymap.command(key_event(KeyCode::Char(' '), KeyModifiers::NONE));
+        assert!(matches!(command, Command::FireGuns));
+        assert_eq!(
+            errors,
+            [format!(
+                "invalid keymap file: {}: j is bound to both `fire_guns` and `missile`",
+                path.display()
+            )]
+        );
+    }
+}
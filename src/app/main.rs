@@ -1,63 +1,359 @@
 use super::{
+    color::{set_mode, ColorMode},
     command::Command,
-    input::{receive_input_commands, send_input_commands},
+    input::{receive_input_commands, send_input_commands, InputMode},
+    keymap::{default_path as default_keymap_path, load_default_keymap, load_keymap, Keymap},
+    options::Options,
+    replay::{Outcome, Player, Recorder},
+    save::{default_path as default_save_path, delete, SavedGame},
+    state::StateMachine,
+    theme::{default_directory as default_themes_directory, Themes},
 };
 use crate::{
-    clock::ticker::Ticker,
-    game::world::World,
-    view::{render::render, session::Session},
+    clock::{ticker::Ticker, time::Clock},
+    game::{
+        high_scores::{default_path, HighScores},
+        spawner::levels::{builtin_levels, load_levels},
+        world::{World, DEFAULT_REWIND_SECONDS},
+    },
+    view::{
+        render::render,
+        session::Session,
+        sprite::{install, load_sprites, watch_sprites, Frames, Sprite},
+    },
+};
+use anyhow::{anyhow, Result};
+use crossterm::{event::Event, terminal};
+use std::{
+    mem,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::mpsc::{self, Receiver},
+    time::Duration,
 };
-use anyhow::Result;
-
-use std::{sync::mpsc, thread, time::Duration};
 
+const DEFAULT_SPEED: u16 = 100;
 const MAIN_LOOP_MIN_PERIOD_MS: u64 = 20;
-const TICK_PERIOD_MS: u64 = 100;
+const TICK_PERIOD_MS: u64 = 100; // At the normal speed
 pub const TICKS_PER_SECOND: u16 = 1000 / TICK_PERIOD_MS as u16;
 
 pub struct App {
+    clock: Box<dyn Clock>,
+    keymap: Rc<Keymap>,
+    load_errors: Vec<String>, // Invalid files that didn't stop the game
+    player: Option<Player>,   // Replaces the keyboard while a replay is being played
+    recorder: Option<Recorder>,
+    replay_result: Option<Result<()>>, // Whether the replay ended in the same way as the recording
+    save_path: Option<PathBuf>,        // `None` while watching a replay
+    saved_game: Option<SavedGame>,     // Until it's continued
+    sprite_updates: Option<Receiver<Result<(Sprite, Frames)>>>, // While sprites are being watched
+    state: StateMachine,
     ticker: Ticker,
     world: World,
 }
 
-impl Default for App {
-    fn default() -> Self {
-        Self {
-            ticker: Ticker::new(Duration::from_millis(TICK_PERIOD_MS)),
-            world: World::default(),
+impl App {
+    // Files are loaded and validated here, so that errors can be reported before the TUI session begins.
+    pub fn new(options: &Options, clock: Box<dyn Clock>) -> Result<Self> {
+        let levels = match &options.levels {
+            Some(directory) => load_levels(directory)?,
+            None => builtin_levels(),
+        };
+        // Files that were given as options must be valid, but problems with the default files are
+        // reported when the application exits.
+        let mut load_errors = Vec::new();
+        let keymap = match &options.keymap {
+            Some(path) => load_keymap(path, true)?,
+            None => match default_keymap_path() {
+                Some(path) => load_default_keymap(&path, &mut load_errors),
+                None => Keymap::default(),
+            },
+        };
+        let keymap = Rc::new(keymap);
+        let mut themes = Themes::load(default_themes_directory().as_deref(), &mut load_errors);
+        if let Some(name) = &options.theme {
+            themes.select(name)?;
         }
+        themes.activate();
+        // Sprites are reloaded whenever they're edited, but only in debug builds.
+        let sprite_updates = match &options.sprites {
+            Some(directory) => {
+                load_sprites(directory)?;
+                cfg!(debug_assertions).then(|| watch_sprites(directory.clone()))
+            }
+            None => None,
+        };
+        set_mode(options.color.unwrap_or_else(ColorMode::detect));
+        let player = match &options.replay {
+            Some(path) => Some(load_player(path)?),
+            None => None,
+        };
+        // Replays don't affect the high scores.
+        let (seed, rewind_seconds, high_scores) = match &player {
+            Some(player) => {
+                let replay = player.replay();
+                let high_scores = HighScores::default();
+                (Some(replay.seed), replay.rewind_seconds, high_scores)
+            }
+            None => {
+                let rewind_seconds = options.rewind_seconds.unwrap_or(DEFAULT_REWIND_SECONDS);
+                (
+                    options.seed,
+                    rewind_seconds,
+                    HighScores::load(default_path(), &mut load_errors),
+                )
+            }
+        };
+        // A saved game that can't be continued is explained on the title menu, rather than
+        // preventing the game from starting.
+        let save_path = player.is_none().then(default_save_path).flatten();
+        let (saved_game, notice) = match save_path.as_deref().map(SavedGame::load) {
+            Some(Ok(saved_game)) => (saved_game, None),
+            Some(Err(error)) => (None, Some(format!("Cannot continue: {error}"))),
+            None => (None, None),
+        };
+        let speed = options.speed.unwrap_or(DEFAULT_SPEED);
+        Ok(Self {
+            clock,
+            keymap: keymap.clone(),
+            load_errors,
+            player,
+            recorder: options.record.clone().map(Recorder::new),
+            replay_result: None,
+            save_path,
+            state: StateMachine::new(options.auto_pause, speed, themes)
+                .with_saved_game(saved_game.is_some(), notice),
+            saved_game,
+            sprite_updates,
+            ticker: Ticker::new(tick_rate(speed)),
+            world: World::new(seed, levels.into(), high_scores, keymap, rewind_seconds),
+        })
     }
-}
 
-impl App {
     pub fn run(&mut self, session: &mut Session) -> Result<()> {
         let min_period = Duration::from_millis(MAIN_LOOP_MIN_PERIOD_MS);
         let (tx, rx) = mpsc::channel();
         send_input_commands(tx);
+        let mut start_commands = if self.player.is_some() {
+            self.state.play(&self.world)
+        } else {
+            Vec::new()
+        };
 
         loop {
-            let ticked = self.ticker.maybe_tick();
+            self.reload_sprites();
+            self.state.handle_world(&self.world);
+            if !self.state.playing() {
+                self.finish_recording()?;
+            }
+            if self.finish_replay() {
+                return Ok(()); // The recording ended when the player quit
+            }
+
+            let tick = self.ticker.number();
+            self.ticker.set_tick_rate(tick_rate(self.state.speed()));
+            let ticked = if !self.state.ticking() {
+                self.ticker.pause();
+                false
+            } else if self
+                .player
+                .as_ref()
+                .is_some_and(|player| player.pending(tick))
+            {
+                false // Wait until all of the recorded input for this tick has been applied
+            } else {
+                self.ticker.maybe_tick(self.clock.now())
+            };
             if ticked {
                 self.world.handle_tick(&self.ticker);
             }
 
-            let commands = receive_input_commands(&rx);
+            // Input is applied separately from ticks, so that replays apply it in the same order.
+            let mut commands = mem::take(&mut start_commands);
+            if !ticked {
+                commands.extend(self.receive_commands(&rx, tick)?);
+            }
             for command in commands.iter() {
                 match command {
-                    Command::Quit => return Ok(()),
-                    Command::Restart => self.ticker.restart(),
+                    Command::LoadGame => self.load_game(),
+                    Command::Quit => {
+                        self.save_game()?;
+                        return self.finish_recording();
+                    }
+                    Command::Restart => {
+                        self.finish_recording()?;
+                        self.ticker.restart();
+                    }
                     _ => (),
                 }
             }
+            // The World is replaced by the saved game, rather than handling it.
+            commands.retain(|command| *command != Command::LoadGame);
 
-            if ticked || !commands.is_empty() {
-                self.world.broadcast_commands(&commands)?;
-                render(session, &mut self.world)?;
+            // Keep rendering while the World isn't ticking, so that menus and resizing the
+            // terminal are handled.
+            if ticked || !commands.is_empty() || !self.state.ticking() {
+                self.world.broadcast_commands(&commands);
+                self.record(&commands)?;
+                let menu = self.state.menu(&self.world).cloned();
+                render(&mut session.terminal, &mut self.world, menu.as_ref())?;
 
                 // A tick, command, or render can cause a collision.
-                self.world.broadcast_collisions()?;
+                if self.state.ticking() {
+                    self.world.broadcast_collisions();
+                }
+            }
+            // Catch up after a stall without sleeping, one tick per iteration, so that each tick is
+            // still followed by rendering and collisions.
+            if !self.ticker.behind() {
+                self.clock.sleep(min_period);
             }
-            thread::sleep(min_period);
         }
     }
+
+    // Problems that didn't stop the game, such as unhandled commands.
+    pub fn diagnostics(&self) -> Vec<String> {
+        self.world.diagnostics().report()
+    }
+
+    // Files that couldn't be loaded or saved, which the player needs to know about, because the
+    // game continued without them.
+    pub fn file_errors(&self) -> Vec<String> {
+        let mut errors = self.load_errors.clone();
+        errors.extend_from_slice(self.world.save_errors());
+        errors
+    }
+
+    // The result of comparing a replay to its recording, if the replay was played to the end.
+    pub fn take_replay_result(&mut self) -> Option<Result<()>> {
+        self.replay_result.take()
+    }
+
+    // Once the recorded game has ended, compare the replay to it, and return control to the player.
+    // Returns whether to quit, because the recording ended when the player quit.
+    fn finish_replay(&mut self) -> bool {
+        let Some(player) = &self.player else {
+            return false;
+        };
+        if !self.world.game_over() && !player.finished(self.ticker.number()) {
+            return false;
+        }
+        let outcome = self.outcome();
+        self.replay_result = Some(player.verify(outcome));
+        self.player = None;
+        !outcome.game_over
+    }
+
+    fn finish_recording(&mut self) -> Result<()> {
+        let outcome = self.outcome();
+        match &mut self.recorder {
+            Some(recorder) if recorder.recording() => recorder.finish(outcome),
+            _ => Ok(()),
+        }
+    }
+
+    // Draw the sprites that were edited since the last iteration. Sprites that are invalid are
+    // reported when the application exits, and the previous sprites are drawn until they're fixed.
+    fn reload_sprites(&mut self) {
+        let Some(updates) = &self.sprite_updates else {
+            return;
+        };
+        for update in updates.try_iter() {
+            match update {
+                Ok((sprite, frames)) => install(sprite, frames),
+                Err(error) => self.load_errors.push(format!("{error:#}")),
+            }
+        }
+    }
+
+    // Continue the saved game, which is then deleted, so that it can't be continued twice.
+    fn load_game(&mut self) {
+        let Some(saved_game) = self.saved_game.take() else {
+            return;
+        };
+        self.world.restore(saved_game.world);
+        self.ticker.restore(saved_game.ticker);
+        if let Some(path) = &self.save_path {
+            // Failing to delete is not fatal, and can't be reported while the TUI is active.
+            delete(path).ok();
+        }
+    }
+
+    // Save the game in progress when the player quits, so that it can be continued next time.
+    fn save_game(&self) -> Result<()> {
+        match &self.save_path {
+            Some(path) if self.state.playing() && !self.world.game_over() => {
+                SavedGame::new(self.ticker.clone(), self.world.save()).save(path)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn outcome(&self) -> Outcome {
+        Outcome {
+            game_over: self.world.game_over(),
+            score: self.world.score(),
+            ticks: self.ticker.number(),
+        }
+    }
+
+    fn receive_commands(&mut self, rx: &Receiver<Event>, tick: u64) -> Result<Vec<Command>> {
+        let Some(player) = &mut self.player else {
+            let mode = self.state.input_mode(&self.world);
+            let auto_pause = self.state.auto_pause();
+            let mut commands = Vec::new();
+            for command in receive_input_commands(rx, mode, &self.keymap, auto_pause) {
+                commands.extend(self.state.handle_command(command, &self.world));
+            }
+            return Ok(commands);
+        };
+        // The recorded commands have already been through the StateMachine. A replay that can't
+        // continue is stopped, and the reason is reported after the TUI session ends.
+        let mut commands = receive_input_commands(rx, InputMode::Replay, &self.keymap, false);
+        match player.next_commands(tick, terminal::size()?) {
+            Ok(recorded) => commands.extend(recorded.unwrap_or_default()),
+            Err(error) => {
+                self.replay_result = Some(Err(error));
+                self.player = None;
+                commands.push(Command::Quit);
+            }
+        }
+        Ok(commands)
+    }
+
+    // A new recording begins after the World is restarted, once the seed is known.
+    fn record(&mut self, commands: &[Command]) -> Result<()> {
+        let Some(recorder) = &mut self.recorder else {
+            return Ok(());
+        };
+        if commands.contains(&Command::Restart) {
+            let (seed, rewind_seconds) = (self.world.seed(), self.world.rewind_seconds());
+            recorder.start(seed, rewind_seconds, terminal::size()?);
+        } else {
+            recorder.record(self.ticker.number(), commands, terminal::size()?);
+        }
+        Ok(())
+    }
+}
+
+fn tick_rate(speed: u16) -> Duration {
+    Duration::from_millis(TICK_PERIOD_MS * 100 / u64::from(speed))
+}
+
+// Replays must be played in a terminal of the same size, because it determines the size of the
+// World's viewports.
+fn load_player(path: &Path) -> Result<Player> {
+    let player = Player::load(path)?;
+    let replay = player.replay();
+    let size = terminal::size()?;
+    if size != (replay.width, replay.height) {
+        return Err(anyhow!(
+            "the replay was recorded in a {}x{} terminal, but this terminal is {}x{}",
+            replay.width,
+            replay.height,
+            size.0,
+            size.1
+        ));
+    }
+    Ok(player)
 }
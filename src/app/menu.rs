@@ -0,0 +1,129 @@
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MenuItem {
+    AutoPause(bool),
+    Back,
+    Continue,
+    HighScores,
+    Play,
+    Quit,
+    Restart,
+    Resume,
+    Settings,
+    Speed,
+    Theme,
+    Title,
+}
+
+impl MenuItem {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::AutoPause(true) => "Auto-pause: on",
+            Self::AutoPause(false) => "Auto-pause: off",
+            Self::Back => "Back",
+            Self::Continue => "Continue",
+            Self::HighScores => "High scores",
+            Self::Play => "Play",
+            Self::Quit => "Quit",
+            Self::Restart => "Restart",
+            Self::Resume => "Resume",
+            Self::Settings => "Settings",
+            Self::Speed => "Speed",
+            Self::Theme => "Theme",
+            Self::Title => "Quit to title",
+        }
+    }
+}
+
+// A list of items, one of which is selected, optionally below some lines of text.
+#[derive(Clone, Debug)]
+pub struct Menu {
+    body: Vec<String>,
+    items: Vec<MenuItem>,
+    labels: Vec<String>,
+    selected: usize,
+    title: &'static str,
+}
+
+impl Menu {
+    pub fn new(title: &'static str, items: Vec<MenuItem>) -> Self {
+        assert!(!items.is_empty());
+        Self {
+            body: Vec::new(),
+            labels: items.iter().map(|item| item.label().to_string()).collect(),
+            items,
+            selected: 0,
+            title,
+        }
+    }
+
+    pub fn with_body(mut self, body: Vec<String>) -> Self {
+        self.body = body;
+        self
+    }
+
+    // Replace an item's label, e.g. to show the current value of a setting.
+    pub fn with_label(mut self, item: MenuItem, label: String) -> Self {
+        if let Some(i) = self.items.iter().position(|other| *other == item) {
+            self.labels[i] = label;
+        }
+        self
+    }
+
+    pub fn body(&self) -> &[String] {
+        &self.body
+    }
+
+    // The items and their labels.
+    pub fn entries(&self) -> impl Iterator<Item = (MenuItem, &str)> {
+        self.items
+            .iter()
+            .copied()
+            .zip(self.labels.iter().map(String::as_str))
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    // Move the selection down, wrapping around to the top.
+    pub fn down(&mut self) {
+        self.selected = (self.selected + 1) % self.items.len();
+    }
+
+    // Move the selection up, wrapping around to the bottom.
+    pub fn up(&mut self) {
+        self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+    }
+
+    pub fn selected(&self) -> MenuItem {
+        self.items[self.selected]
+    }
+
+    pub fn selected_index(&self) -> usize {
+        self.selected
+    }
+
+    pub fn select_index(&mut self, index: usize) {
+        self.selected = index.min(self.items.len() - 1);
+    }
+
+    pub fn title(&self) -> &'static str {
+        self.title
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_wraps_around() {
+        let mut menu = Menu::new("Title", vec![MenuItem::Play, MenuItem::Quit]);
+
+        menu.up();
+        assert_eq!(menu.selected(), MenuItem::Quit);
+
+        menu.down();
+        assert_eq!(menu.selected(), MenuItem::Play);
+    }
+}
@@ -0,0 +1,223 @@
+use super::color::ColorMode;
+use anyhow::{anyhow, Context, Result};
+use std::{env, ops::RangeInclusive, path::PathBuf};
+
+static USAGE: &str = "\
+Usage: space_time_rewind [OPTIONS]
+
+Options:
+  --auto-pause          Pause when the terminal loses focus, if the terminal supports focus events
+  --color <MODE>        Use truecolor, 256, 16, or none (bold and dim text) instead of detecting colors
+  --keymap <FILE>       Load key bindings from a file instead of ~/.config/space_time_rewind/keymap.toml
+  --levels <DIR>        Load levels from the *.toml files in a directory instead of the built-in levels
+  --record <FILE>       Record the input of each game to a replay file, replacing the previous game
+  --replay <FILE>       Replay a recorded game, and report whether it ended in the same way
+  --rewind-seconds <N>  Keep up to N seconds of the game to rewind, from 1 to 30 (default: 5)
+  --seed <NUMBER>       Seed the random number generator to replay a previous game
+  --speed <PCT>         Play at a percentage of the normal speed, from 25 to 400 (default: 100)
+  --sprites <DIR>       Load sprites from the *.txt files in a directory instead of the built-in sprites
+  --theme <NAME>        Use a built-in or user theme by name, or load a theme from a *.toml file
+  -h, --help            Print this help message";
+
+const REWIND_SECONDS_RANGE: RangeInclusive<u16> = 1..=30;
+const SPEED_RANGE: RangeInclusive<u16> = 25..=400;
+
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Options {
+    pub auto_pause: bool,
+    pub color: Option<ColorMode>,
+    pub help: bool,
+    pub keymap: Option<PathBuf>,
+    pub levels: Option<PathBuf>,
+    pub record: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    pub rewind_seconds: Option<u16>,
+    pub seed: Option<u64>,
+    pub speed: Option<u16>,
+    pub sprites: Option<PathBuf>,
+    pub theme: Option<String>,
+}
+
+impl Options {
+    pub fn from_args() -> Result<Self> {
+        parse(env::args().skip(1))
+    }
+
+    pub fn usage() -> &'static str {
+        USAGE
+    }
+}
+
+fn parse(mut args: impl Iterator<Item = String>) -> Result<Options> {
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--auto-pause" => options.auto_pause = true,
+            "--color" => {
+                let value = value(&arg, &mut args)?;
+                let mode = value
+                    .parse()
+                    .with_context(|| format!("invalid --color value: {value}"))?;
+                options.color = Some(mode);
+            }
+            "-h" | "--help" => options.help = true,
+            "--keymap" => options.keymap = Some(PathBuf::from(value(&arg, &mut args)?)),
+            "--levels" => options.levels = Some(PathBuf::from(value(&arg, &mut args)?)),
+            "--record" => options.record = Some(PathBuf::from(value(&arg, &mut args)?)),
+            "--replay" => options.replay = Some(PathBuf::from(value(&arg, &mut args)?)),
+            "--rewind-seconds" => {
+                let value = value(&arg, &mut args)?;
+                let seconds = value
+                    .parse()
+                    .ok()
+                    .filter(|seconds| REWIND_SECONDS_RANGE.contains(seconds))
+                    .ok_or_else(|| {
+                        anyhow!("invalid --rewind-seconds value: {value}, expected 1 to 30")
+                    })?;
+                options.rewind_seconds = Some(seconds);
+            }
+            "--seed" => {
+                let value = value(&arg, &mut args)?;
+                let seed = value
+                    .parse()
+                    .with_context(|| format!("invalid --seed value: {value}"))?;
+                options.seed = Some(seed);
+            }
+            "--speed" => {
+                let value = value(&arg, &mut args)?;
+                let speed = value
+                    .parse()
+                    .ok()
+                    .filter(|speed| SPEED_RANGE.contains(speed))
+                    .ok_or_else(|| anyhow!("invalid --speed value: {value}, expected 25 to 400"))?;
+                options.speed = Some(speed);
+            }
+            "--sprites" => options.sprites = Some(PathBuf::from(value(&arg, &mut args)?)),
+            "--theme" => options.theme = Some(value(&arg, &mut args)?),
+            _ => return Err(anyhow!("unexpected argument: {arg}\n\n{USAGE}")),
+        }
+    }
+    // A replay uses the seed and rewind window that it was recorded with.
+    if options.replay.is_some()
+        && (options.record.is_some() || options.rewind_seconds.is_some() || options.seed.is_some())
+    {
+        return Err(anyhow!(
+            "--replay can't be used with --record, --rewind-seconds, or --seed\n\n{USAGE}"
+        ));
+    }
+    Ok(options)
+}
+
+fn value(arg: &str, args: &mut impl Iterator<Item = String>) -> Result<String> {
+    args.next()
+        .ok_or_else(|| anyhow!("{arg} requires a value\n\n{USAGE}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_returns_defaults_when_empty() {
+        let options = parse_strs(&[]).unwrap();
+
+        assert_eq!(options, Options::default());
+    }
+
+    #[test]
+    fn parse_seed() {
+        let options = parse_strs(&["--seed", "123"]).unwrap();
+
+        assert_eq!(options.seed, Some(123));
+    }
+
+    #[test]
+    fn parse_levels() {
+        let options = parse_strs(&["--levels", "levels/"]).unwrap();
+
+        assert_eq!(options.levels, Some(PathBuf::from("levels/")));
+    }
+
+    #[test]
+    fn parse_auto_pause() {
+        let options = parse_strs(&["--auto-pause"]).unwrap();
+
+        assert!(options.auto_pause);
+    }
+
+    #[test]
+    fn parse_keymap() {
+        let options = parse_strs(&["--keymap", "keymap.toml"]).unwrap();
+
+        assert_eq!(options.keymap, Some(PathBuf::from("keymap.toml")));
+    }
+
+    #[test]
+    fn parse_color() {
+        let options = parse_strs(&["--color", "256"]).unwrap();
+
+        assert_eq!(options.color, Some(ColorMode::Ansi256));
+        assert!(parse_strs(&["--color", "lots"]).is_err());
+    }
+
+    #[test]
+    fn parse_replay_fails_when_recording() {
+        let options = parse_strs(&["--replay", "game.toml"]).unwrap();
+        assert_eq!(options.replay, Some(PathBuf::from("game.toml")));
+
+        assert!(parse_strs(&["--replay", "game.toml", "--record", "other.toml"]).is_err());
+        assert!(parse_strs(&["--seed", "1", "--replay", "game.toml"]).is_err());
+        assert!(parse_strs(&["--rewind-seconds", "9", "--replay", "game.toml"]).is_err());
+    }
+
+    #[test]
+    fn parse_rewind_seconds() {
+        let options = parse_strs(&["--rewind-seconds", "10"]).unwrap();
+
+        assert_eq!(options.rewind_seconds, Some(10));
+        assert!(parse_strs(&["--rewind-seconds", "0"]).is_err());
+        assert!(parse_strs(&["--rewind-seconds", "31"]).is_err());
+    }
+
+    #[test]
+    fn parse_speed() {
+        let options = parse_strs(&["--speed", "150"]).unwrap();
+
+        assert_eq!(options.speed, Some(150));
+        assert!(parse_strs(&["--speed", "0"]).is_err());
+        assert!(parse_strs(&["--speed", "fast"]).is_err());
+    }
+
+    #[test]
+    fn parse_sprites() {
+        let options = parse_strs(&["--sprites", "sprites/"]).unwrap();
+
+        assert_eq!(options.sprites, Some(PathBuf::from("sprites/")));
+    }
+
+    #[test]
+    fn parse_theme() {
+        let options = parse_strs(&["--theme", "solarized"]).unwrap();
+
+        assert_eq!(options.theme.as_deref(), Some("solarized"));
+    }
+
+    #[test]
+    fn parse_seed_fails_when_value_is_missing() {
+        assert!(parse_strs(&["--seed"]).is_err());
+    }
+
+    #[test]
+    fn parse_seed_fails_when_value_is_not_a_number() {
+        assert!(parse_strs(&["--seed", "abc"]).is_err());
+    }
+
+    #[test]
+    fn parse_fails_when_argument_is_unexpected() {
+        assert!(parse_strs(&["--unexpected"]).is_err());
+    }
+
+    fn parse_strs(args: &[&str]) -> Result<Options> {
+        parse(args.iter().map(|arg| (*arg).to_string()))
+    }
+}
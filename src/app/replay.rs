@@ -0,0 +1,396 @@
+use super::{command::Command, keymap::Action};
+use crate::game::world::DEFAULT_REWIND_SECONDS;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+static VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// A recorded game, which can be replayed by starting from the same seed, with the same rewind
+// window, in a terminal of the same size, and applying the same inputs after the same ticks.
+// `width` and `height` are the size that the recording started at, and resizes are inputs.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Replay {
+    pub version: String, // Of the game, because any change to the game can cause a desync
+    pub seed: u64,
+    #[serde(default = "default_rewind_seconds")] // Recordings from before it could be changed
+    pub rewind_seconds: u16,
+    pub width: u16,
+    pub height: u16,
+    pub outcome: Outcome,
+    #[serde(default)]
+    pub inputs: Vec<Input>,
+}
+
+// How the recorded game ended.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Outcome {
+    pub game_over: bool, // Otherwise the player quit
+    pub score: u32,
+    pub ticks: u64,
+}
+
+// The actions that were applied together after a tick, and the terminal's new size if it was
+// resized, because it determines the size of the World's viewports.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Input {
+    pub tick: u64,
+    #[serde(default)]
+    pub actions: Vec<Action>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<(u16, u16)>,
+}
+
+// Records the player's input while a game is being played.
+pub struct Recorder {
+    path: PathBuf,
+    replay: Option<Replay>, // `None` while no game is being played
+}
+
+impl Recorder {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, replay: None }
+    }
+
+    pub fn recording(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    pub fn start(&mut self, seed: u64, rewind_seconds: u16, (width, height): (u16, u16)) {
+        self.replay = Some(Replay {
+            version: VERSION.to_string(),
+            seed,
+            rewind_seconds,
+            width,
+            height,
+            outcome: Outcome {
+                game_over: false,
+                score: 0,
+                ticks: 0,
+            },
+            inputs: Vec::new(),
+        });
+    }
+
+    pub fn record(&mut self, tick: u64, commands: &[Command], size: (u16, u16)) {
+        let Some(replay) = &mut self.replay else {
+            return;
+        };
+        let actions: Vec<Action> = commands
+            .iter()
+            .filter_map(|command| Action::from_command(*command))
+            .collect();
+        let size = (size != replay.size()).then_some(size);
+        if !actions.is_empty() || size.is_some() {
+            replay.inputs.push(Input {
+                tick,
+                actions,
+                size,
+            });
+        }
+    }
+
+    // Save the game that was being recorded, replacing any previously saved game.
+    pub fn finish(&mut self, outcome: Outcome) -> Result<()> {
+        let Some(mut replay) = self.replay.take() else {
+            return Ok(());
+        };
+        replay.outcome = outcome;
+        let text = toml::to_string(&replay)?;
+        fs::write(&self.path, text)
+            .with_context(|| format!("cannot write replay file: {}", self.path.display()))
+    }
+}
+
+// Feeds the recorded input back to the World.
+pub struct Player {
+    next: usize, // The index of the next input
+    replay: Replay,
+}
+
+impl Player {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("cannot read replay file: {}", path.display()))?;
+        let replay =
+            parse(&text).with_context(|| format!("invalid replay file: {}", path.display()))?;
+        Ok(Self { next: 0, replay })
+    }
+
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    // Whether all of the input has been applied, and the recorded game has ended.
+    pub fn finished(&self, tick: u64) -> bool {
+        self.next == self.replay.inputs.len() && tick >= self.replay.outcome.ticks
+    }
+
+    // The next input to apply after the given tick. The World mustn't tick again until every
+    // input for the current tick has been applied. The replay can't continue if the terminal was
+    // resized during the recording, unless this terminal is now the same size, given by `size`.
+    pub fn next_commands(&mut self, tick: u64, size: (u16, u16)) -> Result<Option<Vec<Command>>> {
+        let Some(input) = self.replay.inputs.get(self.next) else {
+            return Ok(None);
+        };
+        if input.tick != tick {
+            return Ok(None);
+        }
+        if let Some((width, height)) = input.size.filter(|recorded| *recorded != size) {
+            return Err(anyhow!(
+                "replay desynced: the terminal was resized to {width}x{height} after tick {tick}, but this terminal is {}x{}",
+                size.0,
+                size.1
+            ));
+        }
+        self.next += 1;
+        Ok(Some(
+            input
+                .actions
+                .iter()
+                .map(|action| action.command())
+                .collect(),
+        ))
+    }
+
+    pub fn pending(&self, tick: u64) -> bool {
+        self.replay
+            .inputs
+            .get(self.next)
+            .is_some_and(|input| input.tick == tick)
+    }
+
+    // Compare the replayed game to the recorded game.
+    pub fn verify(&self, outcome: Outcome) -> Result<()> {
+        let expected = self.replay.outcome;
+        if outcome == expected {
+            return Ok(());
+        }
+        Err(anyhow!(
+            "replay desynced: expected {} at tick {} with {} points, but got {} at tick {} with {} points",
+            describe(expected),
+            expected.ticks,
+            expected.score,
+            describe(outcome),
+            outcome.ticks,
+            outcome.score
+        ))
+    }
+}
+
+impl Replay {
+    // The size of the terminal after the last recorded resize.
+    fn size(&self) -> (u16, u16) {
+        self.inputs
+            .iter()
+            .rev()
+            .find_map(|input| input.size)
+            .unwrap_or((self.width, self.height))
+    }
+}
+
+fn default_rewind_seconds() -> u16 {
+    DEFAULT_REWIND_SECONDS
+}
+
+fn describe(outcome: Outcome) -> &'static str {
+    if outcome.game_over {
+        "game over"
+    } else {
+        "the end of the recording"
+    }
+}
+
+fn parse(text: &str) -> Result<Replay> {
+    let replay: Replay = toml::from_str(text)?;
+    if replay.version != VERSION {
+        return Err(anyhow!(
+            "recorded with version {}, but this is version {VERSION}",
+            replay.version
+        ));
+    }
+    if let Some(pair) = replay
+        .inputs
+        .windows(2)
+        .find(|pair| pair[0].tick > pair[1].tick)
+    {
+        return Err(anyhow!(
+            "inputs must be in order of their ticks, but tick {} is after tick {}",
+            pair[1].tick,
+            pair[0].tick
+        ));
+    }
+    Ok(replay)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ticker::Ticker;
+    use std::time::Duration;
+
+    const SIZE: (u16, u16) = (100, 40);
+
+    fn record() -> Replay {
+        let mut recorder = Recorder::new(PathBuf::new());
+        recorder.start(42, 3, SIZE);
+        recorder.record(0, &[Command::Restart], SIZE);
+        recorder.record(3, &[Command::FireGuns, Command::MoveShip((-1, 0))], SIZE);
+        recorder.record(3, &[Command::PressRewindButton], SIZE);
+        recorder.replay.clone().unwrap()
+    }
+
+    #[test]
+    fn record_only_keeps_the_players_actions() {
+        let replay = record();
+
+        assert_eq!(
+            replay.inputs,
+            vec![
+                Input {
+                    tick: 3,
+                    actions: vec![Action::FireGuns, Action::MoveLeft],
+                    size: None,
+                },
+                Input {
+                    tick: 3,
+                    actions: vec![Action::Rewind],
+                    size: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_reads_what_was_recorded() {
+        let replay = record();
+        let text = toml::to_string(&replay).unwrap();
+
+        assert_eq!(parse(&text).unwrap(), replay);
+    }
+
+    #[test]
+    fn parse_fails_when_the_version_differs() {
+        let mut replay = record();
+        replay.version = String::from("0.0.0");
+        let text = toml::to_string(&replay).unwrap();
+
+        let message = parse(&text).unwrap_err().to_string();
+        assert!(message.contains("recorded with version 0.0.0"), "{message}");
+    }
+
+    #[test]
+    fn next_commands_applies_each_input_after_its_tick() {
+        let mut player = Player {
+            next: 0,
+            replay: record(),
+        };
+
+        assert!(player.next_commands(2, SIZE).unwrap().is_none());
+        assert!(player.pending(3));
+        assert_eq!(
+            player.next_commands(3, SIZE).unwrap(),
+            Some(vec![Command::FireGuns, Command::MoveShip((-1, 0))])
+        );
+        assert_eq!(
+            player.next_commands(3, SIZE).unwrap(),
+            Some(vec![Command::PressRewindButton])
+        );
+        assert!(!player.pending(3));
+        assert!(player.finished(3));
+    }
+
+    // Each input must be replayed after the same tick that it was recorded after, including the ticks
+    // on either side of the Ticker's wrap-around.
+    #[test]
+    fn inputs_are_replayed_after_their_tick_across_the_wrap() {
+        let mut recorder = Recorder::new(PathBuf::new());
+        recorder.start(42, 3, SIZE);
+        let mut ticker = Ticker::new(Duration::ZERO);
+        for _ in 0..2100 {
+            ticker.tick();
+            recorder.record(ticker.number(), &[Command::FireGuns], SIZE);
+        }
+        let mut player = Player {
+            next: 0,
+            replay: recorder.replay.unwrap(),
+        };
+
+        let mut ticker = Ticker::new(Duration::ZERO);
+        for _ in 0..2100 {
+            ticker.tick();
+            let tick = ticker.number();
+            assert_eq!(
+                player.next_commands(tick, SIZE).unwrap(),
+                Some(vec![Command::FireGuns])
+            );
+            assert!(
+                !player.pending(tick),
+                "more than one input after tick {tick}"
+            );
+        }
+    }
+
+    #[test]
+    fn resizes_are_recorded_and_must_be_replayed_at_the_same_size() {
+        let mut recorder = Recorder::new(PathBuf::new());
+        recorder.start(42, 3, SIZE);
+        recorder.record(1, &[], SIZE);
+        recorder.record(2, &[], (120, 50));
+        recorder.record(3, &[Command::FireGuns], (120, 50));
+        recorder.record(4, &[], SIZE);
+        let replay = recorder.replay.unwrap();
+
+        let sizes: Vec<(u64, Option<(u16, u16)>)> = replay
+            .inputs
+            .iter()
+            .map(|input| (input.tick, input.size))
+            .collect();
+        assert_eq!(sizes, [(2, Some((120, 50))), (3, None), (4, Some(SIZE))]);
+        assert_eq!(parse(&toml::to_string(&replay).unwrap()).unwrap(), replay);
+
+        let mut player = Player { next: 0, replay };
+        assert_eq!(player.next_commands(2, (120, 50)).unwrap(), Some(vec![]));
+        assert_eq!(
+            player.next_commands(3, (120, 50)).unwrap(),
+            Some(vec![Command::FireGuns])
+        );
+        let message = player.next_commands(4, (120, 50)).unwrap_err().to_string();
+        assert_eq!(
+            message,
+            "replay desynced: the terminal was resized to 100x40 after tick 4, but this terminal is 120x50"
+        );
+        assert!(player.pending(4));
+    }
+
+    #[test]
+    fn verify_reports_a_desync() {
+        let mut replay = record();
+        replay.outcome = Outcome {
+            game_over: true,
+            score: 10,
+            ticks: 100,
+        };
+        let player = Player { next: 0, replay };
+
+        assert!(player.verify(player.replay.outcome).is_ok());
+        let message = player
+            .verify(Outcome {
+                game_over: true,
+                score: 8,
+                ticks: 90,
+            })
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            message,
+            "replay desynced: expected game over at tick 100 with 10 points, but got game over at tick 90 with 8 points"
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{
+    clock::ticker::Ticker,
+    game::{high_scores, world::SavedWorld},
+};
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+// Incremented whenever the saved state changes, so that older saves are reported rather than
+// misread.
+const SAVE_VERSION: u32 = 5;
+static FILE_NAME: &str = "save.toml";
+
+// A game in progress, which is saved when the player quits, and can be continued on the next launch.
+#[derive(Deserialize, Serialize)]
+pub struct SavedGame {
+    version: u32,
+    pub ticker: Ticker,
+    pub world: SavedWorld,
+}
+
+// Only the version is read at first, because the rest of an older save can't be parsed.
+#[derive(Deserialize)]
+struct Header {
+    version: u32,
+}
+
+impl SavedGame {
+    pub fn new(ticker: Ticker, world: SavedWorld) -> Self {
+        Self {
+            version: SAVE_VERSION,
+            ticker,
+            world,
+        }
+    }
+
+    // Returns `None` if there's no saved game.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => {
+                return Err(error)
+                    .with_context(|| format!("cannot read save file: {}", path.display()))
+            }
+        };
+        let header: Header = toml::from_str(&text)
+            .with_context(|| format!("invalid save file: {}", path.display()))?;
+        check_version(header.version)?;
+        toml::from_str(&text)
+            .map(Some)
+            .with_context(|| format!("invalid save file: {}", path.display()))
+    }
+
+    // Replace any previously saved game.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)
+                .with_context(|| format!("cannot create directory: {}", directory.display()))?;
+        }
+        let text = toml::to_string(self).context("cannot serialize saved game")?;
+        // Write to a temporary file first, so that the file isn't corrupted if writing fails.
+        let temporary_path = path.with_extension("toml.tmp");
+        fs::write(&temporary_path, text)
+            .with_context(|| format!("cannot write file: {}", temporary_path.display()))?;
+        fs::rename(&temporary_path, path)
+            .with_context(|| format!("cannot write file: {}", path.display()))
+    }
+}
+
+// `save.toml`, next to the high scores.
+pub fn default_path() -> Option<PathBuf> {
+    high_scores::default_path().map(|path| path.with_file_name(FILE_NAME))
+}
+
+// A saved game can only be continued once.
+pub fn delete(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => {
+            Err(error).with_context(|| format!("cannot delete save file: {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_version(version: u32) -> Result<()> {
+    match version.cmp(&SAVE_VERSION) {
+        Ordering::Less => Err(anyhow!("the saved game is from an older version")),
+        Ordering::Equal => Ok(()),
+        Ordering::Greater => Err(anyhow!("the saved game is from a newer version")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{app::command::Command, game::world::World, view::render::broadcast_viewports};
+    use std::{env, time::Duration};
+    use tui::layout::Rect;
+
+    fn new_world() -> World {
+        World::for_test(3)
+    }
+
+    fn play(world: &mut World, ticker: &mut Ticker, ticks: u64) {
+        for i in 0..ticks {
+            ticker.tick();
+            world.handle_tick(ticker);
+            if i % 4 == 0 {
+                world.broadcast_commands(&[Command::FireGuns]);
+            }
+            broadcast_viewports(world, Rect::new(0, 0, 100, 40));
+            world.broadcast_collisions();
+        }
+    }
+
+    fn save_to_text(world: &World, ticker: &Ticker) -> String {
+        toml::to_string(&SavedGame::new(ticker.clone(), world.save())).unwrap()
+    }
+
+    #[test]
+    fn a_continued_game_plays_out_in_the_same_way() {
+        let mut world = new_world();
+        let mut ticker = Ticker::new(Duration::ZERO);
+        world.broadcast_commands(&[Command::Restart]);
+        play(&mut world, &mut ticker, 300);
+
+        let saved: SavedGame = toml::from_str(&save_to_text(&world, &ticker)).unwrap();
+        let mut continued_world = new_world();
+        let mut continued_ticker = Ticker::new(Duration::ZERO);
+        continued_world.restore(saved.world);
+        continued_ticker.restore(saved.ticker);
+
+        assert_eq!(continued_ticker.number(), 300);
+        assert_eq!(continued_world.score(), world.score());
+        play(&mut world, &mut ticker, 300);
+        play(&mut continued_world, &mut continued_ticker, 300);
+        assert_eq!(
+            save_to_text(&continued_world, &continued_ticker),
+            save_to_text(&world, &ticker)
+        );
+    }
+
+    #[test]
+    fn load_explains_why_an_older_save_cannot_be_continued() {
+        let path = env::temp_dir().join(format!("space_time_rewind_save_{}", std::process::id()));
+        fs::write(&path, "version = 0\n[world]\nplayers = 1\n").unwrap();
+
+        let result = SavedGame::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "the saved game is from an older version"
+        );
+    }
+
+    #[test]
+    fn load_returns_none_without_a_saved_game() {
+        let path = env::temp_dir().join("space_time_rewind_missing_save.toml");
+
+        assert!(SavedGame::load(&path).unwrap().is_none());
+        assert!(delete(&path).is_ok());
+    }
+}
@@ -0,0 +1,419 @@
+use super::{
+    command::{Command, NO_COMMANDS},
+    input::InputMode,
+    menu::{Menu, MenuItem},
+    theme::Themes,
+};
+use crate::{game::world::World, view::factory::TITLE};
+
+// The game speeds that can be chosen in the settings, as percentages of the normal speed.
+const SPEEDS: [u16; 5] = [50, 75, 100, 150, 200];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum State {
+    GameOver,
+    HighScores,
+    Paused,
+    Playing,
+    Settings,
+    Title,
+}
+
+// Decides which commands reach the World, and which menu is shown, depending on the state of the
+// application. Menu choices such as restarting or quitting are returned as commands for the App.
+pub struct StateMachine {
+    auto_pause: bool,
+    continuable: bool, // Whether there's a saved game
+    menu: Option<Menu>,
+    notice: Option<String>, // Shown on the title menu, e.g. if the saved game can't be continued
+    previous: State,        // Returned to when leaving the high scores or settings
+    speed: u16,             // A percentage of the normal speed
+    state: State,
+    themes: Themes,
+}
+
+impl StateMachine {
+    pub fn new(auto_pause: bool, speed: u16, themes: Themes) -> Self {
+        Self {
+            auto_pause,
+            continuable: false,
+            menu: Some(title_menu(false, None)),
+            notice: None,
+            previous: State::Title,
+            speed,
+            state: State::Title,
+            themes,
+        }
+    }
+
+    // Offer to continue the saved game, or explain why it can't be continued.
+    pub fn with_saved_game(mut self, continuable: bool, notice: Option<String>) -> Self {
+        self.continuable = continuable;
+        self.notice = notice;
+        self.menu = Some(title_menu(continuable, self.notice.as_deref()));
+        self
+    }
+
+    pub fn auto_pause(&self) -> bool {
+        self.auto_pause
+    }
+
+    pub fn handle_command(&mut self, command: Command, world: &World) -> Vec<Command> {
+        match (self.state, command) {
+            (_, Command::Quit) => return vec![Command::Quit],
+            // Restarting is only a menu choice, so that it can't happen by accident mid-game.
+            (_, Command::Restart) => (),
+            (State::Playing, Command::Pause | Command::TogglePause) => {
+                self.enter(State::Paused, world);
+            }
+            (State::Paused, Command::TogglePause) => self.enter(State::Playing, world),
+            // Ignore the player's input while time is being rewound.
+            (State::Playing, _) if world.rewinding() => (),
+            (State::Playing, _)
+            | (
+                State::GameOver,
+                Command::DeleteInitial | Command::EnterInitial(_) | Command::SubmitInitials,
+            ) => return vec![command],
+            (_, Command::MenuBack) => self.back(world),
+            (_, Command::MenuDown) => self.menu.iter_mut().for_each(Menu::down),
+            (_, Command::MenuSelect) => return self.select(world),
+            (_, Command::MenuUp) => self.menu.iter_mut().for_each(Menu::up),
+            _ => (),
+        }
+        NO_COMMANDS
+    }
+
+    // Follow changes to the World, such as when the ship is destroyed.
+    pub fn handle_world(&mut self, world: &World) {
+        if self.state == State::Playing && world.game_over() {
+            self.enter(State::GameOver, world);
+        }
+    }
+
+    pub fn input_mode(&self, world: &World) -> InputMode {
+        match self.state {
+            State::GameOver if world.entering_initials() => InputMode::Initials,
+            State::Playing => InputMode::Playing,
+            _ => InputMode::Menu,
+        }
+    }
+
+    // Start playing without going through the menus, e.g. to watch a replay.
+    pub fn play(&mut self, world: &World) -> Vec<Command> {
+        self.enter(State::Playing, world);
+        vec![Command::Restart]
+    }
+
+    // Whether a game is in progress, even if it's paused.
+    pub fn playing(&self) -> bool {
+        matches!(self.state, State::Paused | State::Playing)
+    }
+
+    // The menu isn't shown while the player is entering their initials.
+    pub fn menu(&self, world: &World) -> Option<&Menu> {
+        self.menu.as_ref().filter(|_| !world.entering_initials())
+    }
+
+    pub fn speed(&self) -> u16 {
+        self.speed
+    }
+
+    #[cfg(test)]
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    // Whether the World is updated on every tick. Explosions keep animating after the game is over.
+    pub fn ticking(&self) -> bool {
+        matches!(self.state, State::GameOver | State::Playing)
+    }
+
+    fn back(&mut self, world: &World) {
+        match self.state {
+            State::HighScores | State::Settings => self.enter(self.previous, world),
+            State::Paused => self.enter(State::Playing, world),
+            _ => (),
+        }
+    }
+
+    fn enter(&mut self, state: State, world: &World) {
+        let is_submenu = |state| matches!(state, State::HighScores | State::Settings);
+        if is_submenu(state) && !is_submenu(self.state) {
+            self.previous = self.state;
+        }
+        self.state = state;
+        self.menu = match state {
+            State::GameOver => Some(Menu::new(
+                "Game over!",
+                vec![
+                    MenuItem::Restart,
+                    MenuItem::HighScores,
+                    MenuItem::Title,
+                    MenuItem::Quit,
+                ],
+            )),
+            State::HighScores => Some(high_scores_menu(world)),
+            State::Paused => Some(Menu::new(
+                "Paused",
+                vec![
+                    MenuItem::Resume,
+                    MenuItem::Restart,
+                    MenuItem::Settings,
+                    MenuItem::Title,
+                    MenuItem::Quit,
+                ],
+            )),
+            State::Playing => None,
+            State::Settings => Some(
+                Menu::new(
+                    "Settings",
+                    vec![
+                        MenuItem::AutoPause(self.auto_pause),
+                        MenuItem::Speed,
+                        MenuItem::Theme,
+                        MenuItem::Back,
+                    ],
+                )
+                .with_label(MenuItem::Speed, format!("Speed: {}%", self.speed))
+                .with_label(
+                    MenuItem::Theme,
+                    format!("Theme: {}", self.themes.selected().name()),
+                ),
+            ),
+            State::Title => Some(title_menu(self.continuable, self.notice.as_deref())),
+        };
+    }
+
+    // Rebuild the menu after a setting changes, keeping the same item selected.
+    fn refresh_menu(&mut self, world: &World) {
+        let index = self.menu.as_ref().map_or(0, Menu::selected_index);
+        self.enter(self.state, world);
+        if let Some(menu) = &mut self.menu {
+            menu.select_index(index);
+        }
+    }
+
+    fn select(&mut self, world: &World) -> Vec<Command> {
+        let Some(item) = self.menu.as_ref().map(Menu::selected) else {
+            return NO_COMMANDS;
+        };
+        match item {
+            MenuItem::AutoPause(_) => {
+                self.auto_pause = !self.auto_pause;
+                self.refresh_menu(world);
+            }
+            MenuItem::Back => self.back(world),
+            // The saved game is continued while paused, so that the player can get ready.
+            MenuItem::Continue => {
+                self.continuable = false;
+                self.notice = None;
+                self.enter(State::Paused, world);
+                return vec![Command::LoadGame];
+            }
+            MenuItem::HighScores => self.enter(State::HighScores, world),
+            MenuItem::Play | MenuItem::Restart => {
+                self.notice = None;
+                self.enter(State::Playing, world);
+                return vec![Command::Restart];
+            }
+            MenuItem::Quit => return vec![Command::Quit],
+            MenuItem::Resume => self.enter(State::Playing, world),
+            MenuItem::Settings => self.enter(State::Settings, world),
+            MenuItem::Speed => {
+                self.speed = next_speed(self.speed);
+                self.refresh_menu(world);
+            }
+            MenuItem::Theme => {
+                self.themes.select_next();
+                self.themes.activate();
+                self.refresh_menu(world);
+            }
+            MenuItem::Title => self.enter(State::Title, world),
+        }
+        NO_COMMANDS
+    }
+}
+
+fn high_scores_menu(world: &World) -> Menu {
+    let scores = world.high_scores().scores();
+    let body = if scores.is_empty() {
+        vec![String::from("None yet")]
+    } else {
+        scores
+            .iter()
+            .enumerate()
+            .map(|(i, high_score)| high_score.row(i, &high_score.initials))
+            .collect()
+    };
+    Menu::new("High scores", vec![MenuItem::Back]).with_body(body)
+}
+
+// The next faster speed, wrapping around to the slowest.
+fn next_speed(speed: u16) -> u16 {
+    SPEEDS
+        .into_iter()
+        .find(|other| *other > speed)
+        .unwrap_or(SPEEDS[0])
+}
+
+fn title_menu(continuable: bool, notice: Option<&str>) -> Menu {
+    let mut items = vec![
+        MenuItem::Play,
+        MenuItem::HighScores,
+        MenuItem::Settings,
+        MenuItem::Quit,
+    ];
+    if continuable {
+        items.insert(0, MenuItem::Continue);
+    }
+    let body = notice.map(String::from).into_iter().collect();
+    Menu::new(TITLE, items).with_body(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_world() -> World {
+        World::for_test(0)
+    }
+
+    fn select(state_machine: &mut StateMachine, world: &World, item: MenuItem) -> Vec<Command> {
+        while state_machine.menu(world).unwrap().selected() != item {
+            state_machine.handle_command(Command::MenuDown, world);
+        }
+        state_machine.handle_command(Command::MenuSelect, world)
+    }
+
+    #[test]
+    fn play_restarts_the_world() {
+        let world = new_world();
+        let mut state_machine = StateMachine::new(false, 100, Themes::default());
+
+        let commands = select(&mut state_machine, &world, MenuItem::Play);
+
+        assert!(matches!(commands[..], [Command::Restart]));
+        assert_eq!(state_machine.state(), State::Playing);
+        assert!(state_machine.menu(&world).is_none());
+    }
+
+    #[test]
+    fn restart_is_ignored_while_playing() {
+        let world = new_world();
+        let mut state_machine = StateMachine::new(false, 100, Themes::default());
+        select(&mut state_machine, &world, MenuItem::Play);
+
+        let commands = state_machine.handle_command(Command::Restart, &world);
+
+        assert!(commands.is_empty());
+        assert_eq!(state_machine.state(), State::Playing);
+    }
+
+    #[test]
+    fn pause_and_resume() {
+        let world = new_world();
+        let mut state_machine = StateMachine::new(false, 100, Themes::default());
+        select(&mut state_machine, &world, MenuItem::Play);
+
+        state_machine.handle_command(Command::TogglePause, &world);
+        assert_eq!(state_machine.state(), State::Paused);
+        assert!(!state_machine.ticking());
+
+        // Losing focus doesn't resume a paused game.
+        state_machine.handle_command(Command::Pause, &world);
+        assert_eq!(state_machine.state(), State::Paused);
+
+        state_machine.handle_command(Command::TogglePause, &world);
+        assert_eq!(state_machine.state(), State::Playing);
+    }
+
+    #[test]
+    fn settings_return_to_the_previous_state() {
+        let world = new_world();
+        let mut state_machine = StateMachine::new(false, 100, Themes::default());
+        select(&mut state_machine, &world, MenuItem::Play);
+        state_machine.handle_command(Command::Pause, &world);
+        select(&mut state_machine, &world, MenuItem::Settings);
+
+        select(&mut state_machine, &world, MenuItem::AutoPause(false));
+        assert!(state_machine.auto_pause());
+        assert_eq!(state_machine.state(), State::Settings);
+
+        state_machine.handle_command(Command::MenuBack, &world);
+        assert_eq!(state_machine.state(), State::Paused);
+    }
+
+    #[test]
+    fn speed_cycles_through_the_speeds() {
+        let world = new_world();
+        let mut state_machine = StateMachine::new(false, 120, Themes::default());
+        select(&mut state_machine, &world, MenuItem::Settings);
+
+        select(&mut state_machine, &world, MenuItem::Speed);
+        assert_eq!(state_machine.speed(), 150);
+        select(&mut state_machine, &world, MenuItem::Speed);
+        select(&mut state_machine, &world, MenuItem::Speed);
+        assert_eq!(state_machine.speed(), 50);
+    }
+
+    #[test]
+    fn theme_cycles_through_the_themes() {
+        let world = new_world();
+        let mut state_machine = StateMachine::new(false, 100, Themes::default());
+        select(&mut state_machine, &world, MenuItem::Settings);
+
+        select(&mut state_machine, &world, MenuItem::Theme);
+
+        let menu = state_machine.menu(&world).unwrap();
+        assert_eq!(menu.selected(), MenuItem::Theme);
+        assert!(menu
+            .entries()
+            .any(|(_, label)| label == "Theme: high-contrast"));
+    }
+
+    #[test]
+    fn continue_loads_the_saved_game_while_paused() {
+        let world = new_world();
+        let mut state_machine =
+            StateMachine::new(false, 100, Themes::default()).with_saved_game(true, None);
+        assert_eq!(
+            state_machine.menu(&world).unwrap().selected(),
+            MenuItem::Continue
+        );
+
+        let commands = select(&mut state_machine, &world, MenuItem::Continue);
+
+        assert!(matches!(commands[..], [Command::LoadGame]));
+        assert_eq!(state_machine.state(), State::Paused);
+        select(&mut state_machine, &world, MenuItem::Title);
+        let menu = state_machine.menu(&world).unwrap();
+        assert!(menu.entries().all(|(item, _)| item != MenuItem::Continue));
+    }
+
+    #[test]
+    fn title_menu_explains_why_the_saved_game_cannot_be_continued() {
+        let world = new_world();
+        let notice = String::from("Cannot continue: the saved game is from an older version");
+        let state_machine = StateMachine::new(false, 100, Themes::default())
+            .with_saved_game(false, Some(notice.clone()));
+
+        let menu = state_machine.menu(&world).unwrap();
+        assert_eq!(menu.body(), [notice]);
+        assert_eq!(menu.selected(), MenuItem::Play);
+    }
+
+    #[test]
+    fn game_over_shows_the_game_over_menu() {
+        let mut world = new_world();
+        let mut state_machine = StateMachine::new(false, 100, Themes::default());
+        select(&mut state_machine, &world, MenuItem::Play);
+        world.broadcast_commands(&[Command::GameOver]);
+
+        state_machine.handle_world(&world);
+
+        assert_eq!(state_machine.state(), State::GameOver);
+        assert!(state_machine.ticking());
+        let commands = select(&mut state_machine, &world, MenuItem::Quit);
+        assert!(matches!(commands[..], [Command::Quit]));
+    }
+}
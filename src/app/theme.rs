@@ -0,0 +1,304 @@
+use super::color::{set_palette, ColorTheme, Palette};
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+use tui::style::Color;
+
+static DIRECTORY_NAME: &str = "space_time_rewind/themes";
+
+// The built-in themes are compiled into the binary. The default theme has no overrides.
+static BUILTIN_THEMES: [(&str, &str); 4] = [
+    ("default", ""),
+    (
+        "high-contrast",
+        include_str!("../../themes/high-contrast.toml"),
+    ),
+    ("solarized", include_str!("../../themes/solarized.toml")),
+    ("color-blind", include_str!("../../themes/color-blind.toml")),
+];
+
+static NAMED_COLORS: [(&str, Color); 17] = [
+    ("black", Color::Black),
+    ("blue", Color::Blue),
+    ("cyan", Color::Cyan),
+    ("darkgray", Color::DarkGray),
+    ("gray", Color::Gray),
+    ("green", Color::Green),
+    ("lightblue", Color::LightBlue),
+    ("lightcyan", Color::LightCyan),
+    ("lightgreen", Color::LightGreen),
+    ("lightmagenta", Color::LightMagenta),
+    ("lightred", Color::LightRed),
+    ("lightyellow", Color::LightYellow),
+    ("magenta", Color::Magenta),
+    ("red", Color::Red),
+    ("reset", Color::Reset),
+    ("white", Color::White),
+    ("yellow", Color::Yellow),
+];
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeDefinition {
+    #[serde(default)]
+    colors: BTreeMap<ColorTheme, String>, // Any colors that aren't given are the defaults
+}
+
+// A named set of colors, which override the default colors.
+#[derive(Clone, Debug)]
+pub struct Theme {
+    name: String,
+    palette: Palette,
+}
+
+impl Theme {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+// The available themes, one of which is active.
+#[derive(Clone, Debug)]
+pub struct Themes {
+    selected: usize,
+    themes: Vec<Theme>,
+}
+
+impl Themes {
+    // Load the built-in themes, and the themes in the user's themes directory, if it exists. Files
+    // that can't be loaded are recorded in `errors` and skipped, so that the game can still start.
+    pub fn load(directory: Option<&Path>, errors: &mut Vec<String>) -> Self {
+        let mut themes: Vec<Theme> = BUILTIN_THEMES
+            .iter()
+            .map(|(name, text)| parse(name, text).expect("Built-in themes are valid"))
+            .collect();
+        let paths = match directory.filter(|directory| directory.is_dir()) {
+            Some(directory) => theme_paths(directory).unwrap_or_else(|error| {
+                errors.push(format!("{error:#}"));
+                Vec::new()
+            }),
+            None => Vec::new(),
+        };
+        for path in paths {
+            match load_theme(&path) {
+                Ok(theme) => {
+                    // A user's theme replaces a built-in theme that has the same name.
+                    themes.retain(|other| other.name != theme.name);
+                    themes.push(theme);
+                }
+                Err(error) => errors.push(format!("{error:#}")),
+            }
+        }
+        Self {
+            selected: 0,
+            themes,
+        }
+    }
+
+    // Select a theme by name, or load it from a file if `name` is a path to a `*.toml` file.
+    pub fn select(&mut self, name: &str) -> Result<()> {
+        let path = Path::new(name);
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "toml")
+        {
+            let theme = load_theme(path)?;
+            self.themes.retain(|other| other.name != theme.name);
+            self.themes.push(theme);
+            self.selected = self.themes.len() - 1;
+            return Ok(());
+        }
+        self.selected = self
+            .themes
+            .iter()
+            .position(|theme| theme.name == name)
+            .ok_or_else(|| {
+                let names: Vec<&str> = self.themes.iter().map(Theme::name).collect();
+                anyhow!(
+                    "unknown theme: {name}, expected one of: {}",
+                    names.join(", ")
+                )
+            })?;
+        Ok(())
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % self.themes.len();
+    }
+
+    pub fn selected(&self) -> &Theme {
+        &self.themes[self.selected]
+    }
+
+    // Render using the selected theme's colors from now on.
+    pub fn activate(&self) {
+        set_palette(self.selected().palette.clone());
+    }
+}
+
+impl Default for Themes {
+    fn default() -> Self {
+        Self::load(None, &mut Vec::new())
+    }
+}
+
+// `$XDG_CONFIG_HOME/space_time_rewind/themes/`, which defaults to `~/.config/...`.
+pub fn default_directory() -> Option<PathBuf> {
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(config_home.join(DIRECTORY_NAME))
+}
+
+fn load_theme(path: &Path) -> Result<Theme> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("cannot read theme file: {}", path.display()))?;
+    let name = path.file_stem().unwrap_or_default().to_string_lossy();
+    parse(&name, &text).with_context(|| format!("invalid theme file: {}", path.display()))
+}
+
+fn parse(name: &str, text: &str) -> Result<Theme> {
+    let definition: ThemeDefinition = toml::from_str(text)?;
+    let mut palette = Palette::new();
+    for (color_theme, value) in definition.colors {
+        let color =
+            parse_color(&value).with_context(|| format!("invalid color for {color_theme:?}"))?;
+        palette.insert(color_theme, color);
+    }
+    Ok(Theme {
+        name: name.to_string(),
+        palette,
+    })
+}
+
+// E.g. "#3399cc" or "lightblue".
+fn parse_color(value: &str) -> Result<Color> {
+    if let Some(hex) = value.strip_prefix('#') {
+        let channel = |i: usize| {
+            hex.get(i..i + 2)
+                .and_then(|channel| u8::from_str_radix(channel, 16).ok())
+        };
+        if let (6, Some(r), Some(g), Some(b)) = (hex.len(), channel(0), channel(2), channel(4)) {
+            return Ok(Color::Rgb(r, g, b));
+        }
+    }
+    let lowercase = value.to_lowercase();
+    NAMED_COLORS
+        .iter()
+        .find(|(name, _)| *name == lowercase)
+        .map(|(_, color)| *color)
+        .ok_or_else(|| {
+            let names: Vec<&str> = NAMED_COLORS.iter().map(|(name, _)| *name).collect();
+            anyhow!("{value:?} must be #rrggbb or one of: {}", names.join(", "))
+        })
+}
+
+// The `*.toml` files in the directory, in order of their file names.
+fn theme_paths(directory: &Path) -> Result<Vec<PathBuf>> {
+    let entries = fs::read_dir(directory)
+        .with_context(|| format!("cannot read themes directory: {}", directory.display()))?;
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "toml")
+        {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_themes_are_valid() {
+        let names: Vec<String> = Themes::default()
+            .themes
+            .iter()
+            .map(|theme| theme.name().to_string())
+            .collect();
+
+        assert_eq!(
+            names,
+            vec!["default", "high-contrast", "solarized", "color-blind"]
+        );
+    }
+
+    #[test]
+    fn load_skips_the_invalid_files_in_the_directory() {
+        let directory =
+            env::temp_dir().join(format!("space_time_rewind_themes_{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("dark.toml"), "[colors]\nBg = \"black\"").unwrap();
+        fs::write(directory.join("broken.toml"), "[colors]\nBg = \"nope\"").unwrap();
+
+        let mut errors = Vec::new();
+        let themes = Themes::load(Some(&directory), &mut errors);
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(themes.themes.last().unwrap().name(), "dark");
+        assert!(themes.themes.iter().all(|theme| theme.name() != "broken"));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with(&format!(
+            "invalid theme file: {}: ",
+            directory.join("broken.toml").display()
+        )));
+    }
+
+    #[test]
+    fn parse_overrides_colors() {
+        let theme = parse("test", "[colors]\nShip = \"#3399cc\"\nBg = \"Black\"").unwrap();
+
+        assert_eq!(theme.palette[&ColorTheme::Ship], Color::Rgb(51, 153, 204));
+        assert_eq!(theme.palette[&ColorTheme::Bg], Color::Black);
+        assert!(!theme.palette.contains_key(&ColorTheme::Bullet));
+    }
+
+    #[test]
+    fn parse_lists_the_valid_names_when_a_key_is_unknown() {
+        let error = parse("test", "[colors]\nShipp = \"red\"").unwrap_err();
+
+        let message = error.to_string();
+        assert!(message.contains("unknown variant `Shipp`"), "{message}");
+        assert!(message.contains("`Ship`"), "{message}");
+    }
+
+    #[test]
+    fn parse_color_fails_when_invalid() {
+        assert!(parse_color("#12345").is_err());
+        assert!(parse_color("#gggggg").is_err());
+        assert!(parse_color("chartreuse").is_err());
+    }
+
+    #[test]
+    fn select_fails_when_the_theme_is_unknown() {
+        let mut themes = Themes::default();
+
+        assert!(themes.select("solarized").is_ok());
+        assert_eq!(themes.selected().name(), "solarized");
+        assert!(themes.select("nope").is_err());
+    }
+
+    #[test]
+    fn activate_changes_the_colors() {
+        let mut themes = Themes::default();
+        themes.select("high-contrast").unwrap();
+        themes.activate();
+
+        assert_eq!(Color::from(ColorTheme::Bg), Color::Rgb(0, 0, 0));
+
+        themes.select("default").unwrap();
+        themes.activate();
+        assert_eq!(Color::from(ColorTheme::Bg), Color::Rgb(21, 21, 21));
+    }
+}
@@ -1,2 +1,3 @@
 pub mod countdown;
 pub mod ticker;
+pub mod time;
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+#[derive(Clone, Deserialize, Serialize)]
 pub struct Countdown {
     current: u16,
     starting_from: u16,
@@ -1,9 +1,15 @@
-use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
-use crate::view::viewport::Viewport;
+use crate::{
+    app::command::{Command, NO_COMMANDS},
+    view::viewport::Viewport,
+};
 
 pub trait TickHandler {
-    fn handle_tick(&mut self, _ticker: &Ticker, _world_viewport: Viewport) {}
+    fn handle_tick(&mut self, _ticker: &Ticker, _world_viewport: Viewport) -> Vec<Command> {
+        NO_COMMANDS
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -41,72 +47,167 @@ impl From<Frequency> for u16 {
     }
 }
 
+// `number` wraps to 0 when it reaches this, so that `number()` never repeats.
 const MAX_NUMBER: u16 = 2048;
+// Time beyond this many ticks is dropped, e.g. after the computer was suspended.
+const MAX_CATCH_UP_TICKS: u32 = 10;
 
+// A fixed-timestep accumulator: time is measured by a Clock, and spent a whole tick at a time.
+// Only the counters are saved, because the timing starts afresh when a saved game is continued.
+#[derive(Clone, Deserialize, Serialize)]
 pub struct Ticker {
+    #[serde(skip)]
+    accumulated: Duration, // Time that hasn't been spent on a tick yet
     cycles: u16,
+    #[serde(skip)]
+    last_update: Option<Duration>, // The Clock's time when `maybe_tick()` was last invoked
     number: u16,
-    last_tick: Option<Instant>,
-    tick_rate: Duration,
+    #[serde(skip)]
+    tick_rate: Duration, // Set by the App before each tick
 }
 
 impl Ticker {
     pub fn new(tick_rate: Duration) -> Self {
         Self {
+            accumulated: Duration::ZERO,
             cycles: 0,
+            last_update: None,
             number: 0,
-            last_tick: None,
             tick_rate,
         }
     }
 
+    #[allow(clippy::manual_is_multiple_of)]
     pub fn at(&self, frequency: Frequency) -> bool {
         // `number` is initialized to 0, so this will return true for all `frequency` when `self` is first initialized.
         self.number % u16::from(frequency) == 0
     }
 
-    pub fn maybe_tick(&mut self) -> bool {
-        let last_tick = match self.last_tick {
-            Some(tick) => tick,
-            None => self.reset_last_tick(),
-        };
-        let should_tick = last_tick.elapsed() >= self.tick_rate;
-        if should_tick {
-            self.reset_last_tick();
-            self.tick();
+    // Whether a whole tick or more has accumulated, e.g. after a stall.
+    pub fn behind(&self) -> bool {
+        self.accumulated >= self.tick_rate
+    }
+
+    // Accumulate the time since the previous invocation, and tick once if a whole tick has
+    // accumulated. After a stall, this keeps ticking on each invocation until it has caught up.
+    pub fn maybe_tick(&mut self, now: Duration) -> bool {
+        if let Some(last_update) = self.last_update {
+            self.accumulated += now.saturating_sub(last_update);
         }
-        should_tick
+        self.last_update = Some(now);
+        self.accumulated = self.accumulated.min(self.tick_rate * MAX_CATCH_UP_TICKS);
+        if !self.behind() {
+            return false;
+        }
+        self.accumulated -= self.tick_rate;
+        self.tick();
+        true
     }
 
     pub fn number(&self) -> u64 {
         u64::from(self.cycles) * u64::from(MAX_NUMBER) + u64::from(self.number)
     }
 
+    // Forget the accumulated time and when the last update occurred, so that the time spent paused
+    // doesn't count towards the next tick.
+    pub fn pause(&mut self) {
+        self.accumulated = Duration::ZERO;
+        self.last_update = None;
+    }
+
+    // Continue counting from a saved Ticker. Older saves can have reached MAX_NUMBER.
+    pub fn restore(&mut self, saved: Ticker) {
+        self.pause();
+        self.cycles = saved.cycles + saved.number / MAX_NUMBER;
+        self.number = saved.number % MAX_NUMBER;
+    }
+
     pub fn restart(&mut self) {
+        self.accumulated = Duration::ZERO;
         self.cycles = 0;
         self.number = 0;
-        self.last_tick = None;
+        self.last_update = None;
     }
 
-    pub fn tick(&mut self) {
-        self.number = if self.number == MAX_NUMBER {
-            self.cycles += 1;
-            0
-        } else {
-            self.number + 1
-        };
+    pub fn set_tick_rate(&mut self, tick_rate: Duration) {
+        self.tick_rate = tick_rate;
     }
 
-    fn reset_last_tick(&mut self) -> Instant {
-        let now = Instant::now();
-        self.last_tick = Some(now);
-        now
+    pub fn tick(&mut self) {
+        self.number += 1;
+        if self.number >= MAX_NUMBER {
+            self.cycles += 1;
+            self.number = 0;
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::time::{Clock, ManualClock};
+
+    const TICK_RATE: Duration = Duration::from_millis(100);
+
+    #[test]
+    fn maybe_tick_once_per_tick_rate() {
+        let clock = ManualClock::default();
+        let mut ticker = Ticker::new(TICK_RATE);
+
+        assert!(!ticker.maybe_tick(clock.now()));
+        clock.advance(Duration::from_millis(60));
+        assert!(!ticker.maybe_tick(clock.now()));
+        clock.advance(Duration::from_millis(60));
+        assert!(ticker.maybe_tick(clock.now()));
+        // The remaining 20 ms count towards the next tick.
+        clock.advance(Duration::from_millis(80));
+        assert!(ticker.maybe_tick(clock.now()));
+        assert_eq!(ticker.number(), 2);
+    }
+
+    #[test]
+    fn maybe_tick_catches_up_after_a_stall() {
+        let clock = ManualClock::default();
+        let mut ticker = Ticker::new(TICK_RATE);
+        ticker.maybe_tick(clock.now());
+
+        clock.advance(Duration::from_millis(350));
+        let mut ticks = 0;
+        while ticker.maybe_tick(clock.now()) {
+            ticks += 1;
+        }
+
+        assert_eq!(ticks, 3);
+        assert!(!ticker.behind());
+    }
+
+    #[test]
+    fn maybe_tick_drops_time_after_a_long_stall() {
+        let clock = ManualClock::default();
+        let mut ticker = Ticker::new(TICK_RATE);
+        ticker.maybe_tick(clock.now());
+
+        clock.sleep(Duration::from_secs(60));
+        let mut ticks = 0;
+        while ticker.maybe_tick(clock.now()) {
+            ticks += 1;
+        }
+
+        assert_eq!(ticks, MAX_CATCH_UP_TICKS);
+    }
+
+    #[test]
+    fn pause_ignores_the_time_spent_paused() {
+        let clock = ManualClock::default();
+        let mut ticker = Ticker::new(TICK_RATE);
+        ticker.maybe_tick(clock.now());
+
+        ticker.pause();
+        clock.advance(Duration::from_secs(5));
+
+        assert!(!ticker.maybe_tick(clock.now()));
+        assert_eq!(ticker.number(), 0);
+    }
 
     #[test]
     fn number_is_0_when_new() {
@@ -116,6 +217,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn at_returns_true_for_any_frequency_when_new() {
         let ticker = Ticker::new(Duration::from_secs(1));
 
@@ -135,14 +237,41 @@ mod tests {
     }
 
     #[test]
-    fn restarts_at_0_after_2048() {
+    fn restarts_at_0_after_2047() {
         let mut ticker = Ticker::new(Duration::from_secs(1));
-        ticker.number = 2047;
+        ticker.number = 2046;
 
         ticker.tick();
-        assert_eq!(ticker.number, 2048);
+        assert_eq!(ticker.number, 2047);
         ticker.tick();
         assert_eq!(ticker.number, 0);
+        assert_eq!(ticker.cycles, 1);
+    }
+
+    #[test]
+    fn number_increases_across_the_wrap() {
+        let mut ticker = Ticker::new(Duration::from_secs(1));
+        ticker.number = MAX_NUMBER - 2;
+
+        let numbers: Vec<u64> = (0..3)
+            .map(|_| {
+                ticker.tick();
+                ticker.number()
+            })
+            .collect();
+        assert_eq!(numbers, [2047, 2048, 2049]);
+    }
+
+    #[test]
+    fn restore_carries_a_number_that_reached_max_number() {
+        let mut saved = Ticker::new(Duration::ZERO);
+        saved.cycles = 1;
+        saved.number = MAX_NUMBER;
+        let mut ticker = Ticker::new(Duration::ZERO);
+
+        ticker.restore(saved);
+        assert_eq!((ticker.cycles, ticker.number), (2, 0));
+        assert_eq!(ticker.number(), 2 * u64::from(MAX_NUMBER));
     }
 
     #[test]
@@ -0,0 +1,59 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+// A source of time for the game loop, which can be replaced in tests.
+pub trait Clock {
+    // The time elapsed since the clock was created.
+    fn now(&self) -> Duration;
+
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+// Time only passes when the clock is advanced, or when sleeping, which returns immediately.
+#[cfg(test)]
+#[derive(Default)]
+pub struct ManualClock {
+    now: std::cell::Cell<Duration>,
+}
+
+#[cfg(test)]
+impl ManualClock {
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+}
+
+#[cfg(test)]
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
@@ -1,5 +1,12 @@
-mod actors;
+pub mod actors;
+pub mod event_bus;
 pub mod game_item;
-mod spawner;
+pub mod high_scores;
+mod hitbox;
+mod radar;
+mod random;
+mod rewind;
+mod spatial_hash;
+pub mod spawner;
 mod ui;
 pub mod world;
@@ -1,6 +1,10 @@
 pub mod asteroid;
+pub mod boss;
 pub mod bullet;
+pub mod enemy;
 pub mod explosion;
+pub mod high_score_table;
+pub mod level_banner;
 pub mod missile;
 pub mod power_up;
 pub mod ship;
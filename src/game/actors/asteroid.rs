@@ -4,41 +4,45 @@ use crate::{
         command::{Command, CommandHandler, NO_COMMANDS},
     },
     clock::ticker::{Frequency, TickHandler, Ticker},
-    game::game_item::{GameItem, GameItemKind},
+    game::{
+        game_item::{GameItem, GameItemKind, SavedGameItem},
+        hitbox::Hitbox,
+    },
     view::{
+        animation::{Animation, Frame, Playback},
         coordinates::Coordinates,
         render::Renderable,
         renderer::Renderer,
+        sprite::{self, Sprite},
         util::{chars_height, chars_width},
+        velocity::Velocity,
         viewport::Viewport,
     },
 };
+use serde::{Deserialize, Serialize};
 
-static TEXT_LARGE: &str = "\
-\x20▟▒▒▒▓▓▓▒▒▒▓▓▓▓▓▒▓▩
-▜▓▓▛▞▒▒▒▓▓▒▓▒▒▓▟▓▓▓▞
-▜▓▓▒▒▓▟▛▓▛▛▓▓▛▓▛▛▓▓▞
- ▜▓▓▓▒▒▓▟▓▓▓▓▞▓▓▓▓▛
-  ▟▒▒▒▛▟▛▒▛▒▓▓▓▓▓▓▒▓▩
- ▜▓▓▓▓▓▒▛▒▒▓▓▓▒▒▒▒▓▞
-▩▒▓▓▓▟▟▓▓▟▟▓▓▓▟▓▓▒▒▓▛";
-static TEXT_MEDIUM: &str = "\
-\x20▟▒▒▓▩▩
-▜▓▓▓▓▞▟▓▞
-▜▓▓▞▟▓▓▓▓▞
- ▩▒▓▒▒▓▛";
-static TEXT_SMALL: &str = "\
-▟▒▓▩
-▜▓▓▞
-▩▒▓▛";
-
-enum AsteroidSize {
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum AsteroidSize {
     Large,
     Medium,
     Small,
 }
 
 impl AsteroidSize {
+    // A spin through the sprite's frames, which is faster for smaller asteroids.
+    fn animation(&self) -> Animation {
+        let ticks = match self {
+            Self::Large => 4,
+            Self::Medium => 3,
+            Self::Small => 2,
+        };
+        let color = self.color(self.initial_hp());
+        let frames = (0..self.sprite().frames())
+            .map(|index| Frame::new(index, color, ticks))
+            .collect();
+        Animation::new(self.sprite(), Playback::Loop, frames)
+    }
+
     fn color(&self, hp: u8) -> ColorTheme {
         if hp <= self.initial_hp() / 3 {
             return ColorTheme::AsteroidLowHp;
@@ -61,6 +65,15 @@ impl AsteroidSize {
         }
     }
 
+    // Destroyed asteroids break apart into two of this size.
+    fn fragment(&self) -> Option<Self> {
+        match self {
+            Self::Large => Some(Self::Medium),
+            Self::Medium => Some(Self::Small),
+            Self::Small => None,
+        }
+    }
+
     fn initial_hp(&self) -> u8 {
         match self {
             Self::Large => 12,
@@ -73,39 +86,57 @@ impl AsteroidSize {
         u32::from(self.initial_hp())
     }
 
-    fn text(&self) -> &'static str {
+    fn sprite(&self) -> Sprite {
         match self {
-            Self::Large => TEXT_LARGE,
-            Self::Medium => TEXT_MEDIUM,
-            Self::Small => TEXT_SMALL,
+            Self::Large => Sprite::AsteroidLarge,
+            Self::Medium => Sprite::AsteroidMedium,
+            Self::Small => Sprite::AsteroidSmall,
         }
     }
+
+    fn text(&self) -> &'static str {
+        sprite::text(self.sprite())
+    }
 }
 
+#[derive(Clone, Deserialize, Serialize)]
 pub struct Asteroid {
+    animation: Animation,
     coordinates: Coordinates,
     deleted: bool,
+    drift: i16, // Cells moved horizontally for each cell moved down
     height: u8,
     hp: u8,
     kind: AsteroidSize,
-    text: &'static str,
+    velocity: Velocity,
     width: u8,
 }
 
 impl CommandHandler for Asteroid {
     fn handle_command(&mut self, command: Command) -> Vec<Command> {
-        if let Command::Collide(kind) = command {
-            match kind {
-                GameItemKind::Bullet => self.hp = self.hp.saturating_sub(1),
-                GameItemKind::Missile | GameItemKind::Ship => self.hp = 0,
-                _ => (),
-            }
+        if let Command::Collide(kind, _) = command {
+            // Missiles and the ship vaporize asteroids, whereas bullets break them apart.
+            let vaporized = match kind {
+                GameItemKind::Bullet => {
+                    self.hp = self.hp.saturating_sub(1);
+                    false
+                }
+                GameItemKind::Missile | GameItemKind::Ship => {
+                    self.hp = 0;
+                    true
+                }
+                _ => false,
+            };
             if self.hp == 0 {
                 self.deleted = true;
-                return vec![
+                let mut commands = vec![
                     Command::AddExplosion(self.viewport().centered()),
                     Command::IncreaseScore(self.kind.points()),
                 ];
+                if !vaporized {
+                    commands.extend(self.fragments());
+                }
+                return commands;
             }
         }
         NO_COMMANDS
@@ -117,14 +148,27 @@ impl GameItem for Asteroid {
         self.deleted
     }
 
+    fn hitboxes(&self) -> Vec<Hitbox> {
+        vec![Hitbox::new_with_mask(
+            self.viewport(),
+            self.animation.text(),
+        )]
+    }
+
     fn kind(&self) -> GameItemKind {
         GameItemKind::Asteroid
     }
+
+    fn saved(&self) -> SavedGameItem {
+        SavedGameItem::Asteroid(self.clone())
+    }
 }
 
 impl Renderable for Asteroid {
     fn render(&self, renderer: &mut Renderer) {
-        renderer.render_with_offset(self.viewport(), self.text, self.kind.color(self.hp));
+        // Damage overrides the color of the animation's frames.
+        let color = self.kind.color(self.hp);
+        renderer.render_with_offset(self.viewport(), self.animation.text(), color);
     }
 
     fn viewport(&self) -> Viewport {
@@ -133,14 +177,13 @@ impl Renderable for Asteroid {
 }
 
 impl TickHandler for Asteroid {
-    fn handle_tick(&mut self, ticker: &Ticker, world_viewport: Viewport) {
-        if ticker.at(self.kind.frequency()) {
-            self.coordinates.offset_y(-1);
-
-            if !world_viewport.intersects_vertically(self.viewport()) {
-                self.deleted = true;
-            }
+    fn handle_tick(&mut self, _: &Ticker, world_viewport: Viewport) -> Vec<Command> {
+        self.animation.handle_tick(|| ());
+        self.velocity.move_coordinates(&mut self.coordinates);
+        if !world_viewport.intersects_vertically(self.viewport()) {
+            self.deleted = true;
         }
+        NO_COMMANDS
     }
 }
 
@@ -157,16 +200,86 @@ impl Asteroid {
         Self::new(coordinates, AsteroidSize::Small)
     }
 
+    pub fn new_fragment(coordinates: Coordinates, kind: AsteroidSize, drift: i16) -> Self {
+        Self {
+            drift,
+            velocity: Velocity::from_frequency((drift, -1), kind.frequency()),
+            ..Self::new(coordinates, kind)
+        }
+    }
+
     fn new(coordinates: Coordinates, kind: AsteroidSize) -> Self {
         let text = kind.text();
         Self {
+            animation: kind.animation(),
             coordinates,
             deleted: false,
+            drift: 0,
             height: chars_height(text),
             hp: kind.initial_hp(),
             kind,
-            text,
+            velocity: Velocity::from_frequency((0, -1), kind.frequency()),
             width: chars_width(text),
         }
     }
+
+    // Place the fragments at the left and right edges of this asteroid, drifting apart.
+    fn fragments(&self) -> Vec<Command> {
+        let Some(size) = self.kind.fragment() else {
+            return NO_COMMANDS;
+        };
+        let left = self.coordinates;
+        let mut right = self.coordinates;
+        right.offset_x(i16::from(self.width) - i16::from(chars_width(size.text())));
+        vec![
+            Command::AddAsteroid(left, size, self.drift - 1),
+            Command::AddAsteroid(right, size, self.drift + 1),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_asteroid_splits_into_two_medium_asteroids_drifting_apart() {
+        let mut asteroid = Asteroid::new_large(Coordinates::new(10, 10));
+        asteroid.hp = 1;
+
+        let commands = asteroid.handle_command(Command::Collide(GameItemKind::Bullet, 0));
+
+        assert!(asteroid.deleted());
+        assert!(matches!(
+            commands[..],
+            [
+                Command::AddExplosion(_),
+                Command::IncreaseScore(_),
+                Command::AddAsteroid(_, AsteroidSize::Medium, -1),
+                Command::AddAsteroid(_, AsteroidSize::Medium, 1),
+            ]
+        ));
+    }
+
+    #[test]
+    fn small_asteroid_does_not_split() {
+        let mut asteroid = Asteroid::new_small(Coordinates::new(10, 10));
+        asteroid.hp = 1;
+
+        let commands = asteroid.handle_command(Command::Collide(GameItemKind::Bullet, 0));
+
+        assert_eq!(commands.len(), 2);
+    }
+
+    #[test]
+    fn missile_vaporizes_without_splitting() {
+        let mut asteroid = Asteroid::new_large(Coordinates::new(10, 10));
+
+        let commands = asteroid.handle_command(Command::Collide(GameItemKind::Missile, 0));
+
+        assert!(asteroid.deleted());
+        assert!(!commands
+            .iter()
+            .any(|command| matches!(command, Command::AddAsteroid(..))));
+    }
 }
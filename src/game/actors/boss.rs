@@ -0,0 +1,344 @@
+use crate::{
+    app::{
+        color::ColorTheme,
+        command::{Command, CommandHandler, NO_COMMANDS},
+    },
+    clock::ticker::{Frequency, TickHandler, Ticker},
+    game::{
+        game_item::{GameItem, GameItemKind, SavedGameItem},
+        hitbox::Hitbox,
+        radar::{find_ship, Contact},
+    },
+    view::{
+        coordinates::Coordinates,
+        render::Renderable,
+        renderer::Renderer,
+        sprite::{self, Sprite},
+        util::{chars_height, chars_width},
+        velocity::{Velocity, SUBCELLS},
+        viewport::Viewport,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+const CORE_HITBOX: usize = 1;
+const HOVER_Y: i8 = 18; // Stop entering at this y-position
+const MISSILE_DAMAGE: u8 = 5;
+const POINTS: u32 = 100;
+
+// Columns from which volleys are fired, relative to the boss' left edge, and the horizontal
+// velocities of their bullets, so that volleys fan out.
+const CANNONS: [(i16, i16); 3] = [(1, -SUBCELLS / 4), (6, 0), (11, SUBCELLS / 4)];
+
+#[derive(Clone, Copy)]
+enum Step {
+    Move(i16, i16, u16), // dx, dy, number of moves
+    Track(u16),          // Move towards the ship for a number of moves
+    Volley,
+}
+
+// The boss loops through these steps after it has entered the screen.
+static SCRIPT: [Step; 11] = [
+    Step::Track(20),
+    Step::Volley,
+    Step::Move(1, 0, 12),
+    Step::Volley,
+    Step::Move(-1, 0, 24),
+    Step::Volley,
+    Step::Move(1, 0, 12),
+    Step::Move(0, -1, 4),
+    Step::Volley,
+    Step::Move(0, 1, 4),
+    Step::Volley,
+];
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Boss {
+    announced: bool,
+    coordinates: Coordinates,
+    deleted: bool,
+    entering: bool,
+    hp: u8,
+    max_hp: u8,
+    remaining_moves: u16,
+    ship: Option<Viewport>,
+    step: usize,
+    velocity: Velocity, // Set at each move, which lasts for 2 ticks
+}
+
+impl CommandHandler for Boss {
+    fn handle_command(&mut self, command: Command) -> Vec<Command> {
+        let Command::Collide(kind, hitbox) = command else {
+            return NO_COMMANDS;
+        };
+        // Only the core is vulnerable: the wings are armored.
+        if hitbox != CORE_HITBOX {
+            return NO_COMMANDS;
+        }
+        let damage = match kind {
+            GameItemKind::Bullet => 1,
+            GameItemKind::Missile => MISSILE_DAMAGE,
+            _ => return NO_COMMANDS,
+        };
+        self.hp = self.hp.saturating_sub(damage);
+        let mut commands = vec![Command::UpdateBossHealth(self.hp, self.max_hp)];
+        if self.hp == 0 {
+            self.deleted = true;
+            commands.extend(
+                self.parts()
+                    .iter()
+                    .map(|(viewport, _)| Command::AddExplosion(viewport.centered())),
+            );
+            commands.push(Command::IncreaseScore(POINTS));
+            commands.push(Command::BossDefeated);
+        }
+        commands
+    }
+}
+
+impl GameItem for Boss {
+    fn deleted(&self) -> bool {
+        self.deleted
+    }
+
+    fn handle_radar(&mut self, contacts: &[Contact]) {
+        self.ship = find_ship(contacts).map(|contact| contact.viewport);
+        if !self.announced {
+            // Enter the screen directly above the ship, wherever it is in the world.
+            if let Some(ship) = self.ship {
+                let dx = self.viewport().distance_x(ship);
+                self.coordinates.offset_x(dx);
+            }
+        }
+    }
+
+    fn hitboxes(&self) -> Vec<Hitbox> {
+        self.parts()
+            .iter()
+            .map(|(viewport, text)| Hitbox::new_with_mask(*viewport, text))
+            .collect()
+    }
+
+    fn kind(&self) -> GameItemKind {
+        GameItemKind::Enemy
+    }
+
+    fn saved(&self) -> SavedGameItem {
+        SavedGameItem::Boss(self.clone())
+    }
+}
+
+impl Renderable for Boss {
+    fn render(&self, renderer: &mut Renderer) {
+        for (i, (viewport, text)) in self.parts().iter().enumerate() {
+            let color = if i == CORE_HITBOX {
+                ColorTheme::BossCore
+            } else {
+                ColorTheme::BossArmor
+            };
+            renderer.render_with_offset(*viewport, text, color);
+        }
+    }
+
+    fn viewport(&self) -> Viewport {
+        Viewport::new_with_coordinates(
+            width(),
+            chars_height(sprite::text(Sprite::BossCore)),
+            self.coordinates,
+        )
+    }
+}
+
+impl TickHandler for Boss {
+    fn handle_tick(&mut self, ticker: &Ticker, _: Viewport) -> Vec<Command> {
+        let mut commands = Vec::new();
+        if !self.announced {
+            self.announced = true;
+            commands.push(Command::UpdateBossHealth(self.hp, self.max_hp));
+        }
+        if ticker.at(Frequency::Two) {
+            commands.extend(self.next_move());
+        }
+        self.velocity.move_coordinates(&mut self.coordinates);
+        commands
+    }
+}
+
+impl Boss {
+    pub fn new(coordinates: Coordinates, hp: u8) -> Self {
+        Self {
+            announced: false,
+            coordinates,
+            deleted: false,
+            entering: true,
+            hp,
+            max_hp: hp,
+            remaining_moves: moves(SCRIPT[0]),
+            ship: None,
+            step: 0,
+            velocity: Velocity::default(),
+        }
+    }
+
+    // Enter the screen, and then follow the script. Each move sets the velocity, which is reset so
+    // that it moves by exactly one cell per move.
+    fn next_move(&mut self) -> Vec<Command> {
+        let (_, y) = self.coordinates.as_tuple();
+        self.entering = self.entering && y > HOVER_Y;
+        if self.entering {
+            self.velocity = Velocity::from_frequency((self.track(), -1), Frequency::Two);
+            return NO_COMMANDS;
+        }
+
+        let (movement, commands) = match SCRIPT[self.step] {
+            Step::Move(dx, dy, _) => ((dx, dy), NO_COMMANDS),
+            Step::Track(_) => ((self.track(), 0), NO_COMMANDS),
+            Step::Volley => ((0, 0), self.volley()),
+        };
+        self.velocity = Velocity::from_frequency(movement, Frequency::Two);
+        self.remaining_moves = self.remaining_moves.saturating_sub(1);
+        if self.remaining_moves == 0 {
+            self.step = (self.step + 1) % SCRIPT.len();
+            self.remaining_moves = moves(SCRIPT[self.step]);
+        }
+        commands
+    }
+
+    // The left wing, core, and right wing, which are also the boss' hitboxes.
+    fn parts(&self) -> [(Viewport, &'static str); 3] {
+        let core_offset = chars_width(sprite::text(Sprite::BossLeftWing));
+        let right_wing_offset = core_offset + chars_width(sprite::text(Sprite::BossCore));
+        [
+            (
+                part_viewport(sprite::text(Sprite::BossLeftWing), self.coordinates, 0),
+                sprite::text(Sprite::BossLeftWing),
+            ),
+            (
+                part_viewport(
+                    sprite::text(Sprite::BossCore),
+                    self.coordinates,
+                    core_offset,
+                ),
+                sprite::text(Sprite::BossCore),
+            ),
+            (
+                part_viewport(
+                    sprite::text(Sprite::BossRightWing),
+                    self.coordinates,
+                    right_wing_offset,
+                ),
+                sprite::text(Sprite::BossRightWing),
+            ),
+        ]
+    }
+
+    // The horizontal direction towards the ship.
+    fn track(&self) -> i16 {
+        self.ship
+            .map_or(0, |ship| self.viewport().distance_x(ship).signum())
+    }
+
+    fn volley(&self) -> Vec<Command> {
+        CANNONS
+            .iter()
+            .map(|(cannon, dx)| {
+                let mut coordinates = self.coordinates;
+                coordinates.movement((*cannon, -1));
+                Command::AddEnemyBullet(coordinates, *dx)
+            })
+            .collect()
+    }
+}
+
+fn moves(step: Step) -> u16 {
+    match step {
+        Step::Move(_, _, moves) | Step::Track(moves) => moves,
+        Step::Volley => 1,
+    }
+}
+
+fn part_viewport(text: &str, coordinates: Coordinates, x_offset: u8) -> Viewport {
+    let mut coordinates = coordinates;
+    coordinates.offset_x(i16::from(x_offset));
+    Viewport::new_with_coordinates(chars_width(text), chars_height(text), coordinates)
+}
+
+fn width() -> u8 {
+    chars_width(sprite::text(Sprite::BossLeftWing))
+        + chars_width(sprite::text(Sprite::BossCore))
+        + chars_width(sprite::text(Sprite::BossRightWing))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn bullets_do_not_damage_the_wings() {
+        let mut boss = Boss::new(Coordinates::new(0, 20), 10);
+
+        let commands = boss.handle_command(Command::Collide(GameItemKind::Bullet, 0));
+
+        assert!(commands.is_empty());
+        assert_eq!(boss.hp, 10);
+    }
+
+    #[test]
+    fn bullets_damage_the_core() {
+        let mut boss = Boss::new(Coordinates::new(0, 20), 10);
+
+        boss.handle_command(Command::Collide(GameItemKind::Bullet, CORE_HITBOX));
+
+        assert_eq!(boss.hp, 9);
+    }
+
+    #[test]
+    fn is_defeated_when_hp_reaches_0() {
+        let mut boss = Boss::new(Coordinates::new(0, 20), 1);
+
+        let commands = boss.handle_command(Command::Collide(GameItemKind::Missile, CORE_HITBOX));
+
+        assert!(boss.deleted());
+        assert!(commands
+            .iter()
+            .any(|command| matches!(command, Command::BossDefeated)));
+    }
+
+    #[test]
+    fn enters_to_its_hover_height_and_loops_through_its_script_in_place() {
+        let mut boss = Boss::new(Coordinates::new(50, 30), 10);
+        let mut ticker = Ticker::new(Duration::ZERO);
+        let mut tick = |boss: &mut Boss| {
+            ticker.tick();
+            boss.handle_tick(&ticker, Viewport::new_for_world());
+        };
+        while boss.entering {
+            tick(&mut boss);
+        }
+        assert_eq!(boss.coordinates.as_tuple(), (50, HOVER_Y));
+
+        // Each move lasts 2 ticks, and the script's moves add up to nothing.
+        let script_ticks: u16 = SCRIPT.iter().map(|step| moves(*step) * 2).sum();
+        let mut positions = Vec::new();
+        for _ in 0..script_ticks {
+            tick(&mut boss);
+            positions.push(boss.coordinates.as_tuple());
+        }
+        assert_eq!(boss.coordinates.as_tuple(), (50, HOVER_Y));
+        assert!(positions.contains(&(62, HOVER_Y)));
+        assert!(positions.contains(&(38, HOVER_Y)));
+    }
+
+    #[test]
+    fn hitboxes_are_adjacent_and_wrap_around_the_world() {
+        let boss = Boss::new(Coordinates::new(195, 20), 1);
+        let hitboxes = boss.hitboxes();
+
+        assert_eq!(hitboxes[1].viewport.bottom_left().as_tuple(), (199, 20));
+        assert_eq!(hitboxes[2].viewport.bottom_left().as_tuple(), (4, 20));
+        assert!(hitboxes
+            .iter()
+            .all(|hitbox| boss.viewport().intersects(hitbox.viewport)));
+    }
+}
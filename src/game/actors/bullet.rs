@@ -3,30 +3,78 @@ use crate::{
         color::ColorTheme,
         command::{Command, CommandHandler, NO_COMMANDS},
     },
-    clock::ticker::{Frequency, TickHandler, Ticker},
-    game::game_item::{GameItem, GameItemKind},
+    clock::ticker::{TickHandler, Ticker},
+    game::game_item::{GameItem, GameItemKind, SavedGameItem},
     view::{
         coordinates::Coordinates,
         render::Renderable,
         renderer::Renderer,
+        sprite::{self, Sprite},
         util::{chars_height, chars_width},
+        velocity::{Velocity, SUBCELLS},
         viewport::Viewport,
     },
 };
+use serde::{Deserialize, Serialize};
 
-static TEXT: &str = "•";
+#[derive(Clone, Copy, Deserialize, Serialize)]
+enum BulletKind {
+    Enemy,
+    Ship,
+}
+
+impl BulletKind {
+    fn color(self) -> ColorTheme {
+        match self {
+            Self::Enemy => ColorTheme::EnemyBullet,
+            Self::Ship => ColorTheme::Bullet,
+        }
+    }
+
+    // Enemies fire downwards and the ship fires upwards, one cell per tick.
+    fn dy(self) -> i16 {
+        match self {
+            Self::Enemy => -SUBCELLS,
+            Self::Ship => SUBCELLS,
+        }
+    }
+
+    fn game_item_kind(self) -> GameItemKind {
+        match self {
+            Self::Enemy => GameItemKind::EnemyBullet,
+            Self::Ship => GameItemKind::Bullet,
+        }
+    }
+
+    fn hits(self, kind: GameItemKind) -> bool {
+        match self {
+            Self::Enemy => kind.is_ship(),
+            Self::Ship => kind.is_shootable(),
+        }
+    }
 
+    fn text(self) -> &'static str {
+        match self {
+            Self::Enemy => sprite::text(Sprite::EnemyBullet),
+            Self::Ship => sprite::text(Sprite::Bullet),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
 pub struct Bullet {
     coordinates: Coordinates,
     deleted: bool,
     height: u8,
+    kind: BulletKind,
+    velocity: Velocity,
     width: u8,
 }
 
 impl CommandHandler for Bullet {
     fn handle_command(&mut self, command: Command) -> Vec<Command> {
-        if let Command::Collide(kind) = command {
-            if kind.is_shootable() {
+        if let Command::Collide(kind, _) = command {
+            if self.kind.hits(kind) {
                 self.deleted = true
             }
         }
@@ -40,13 +88,17 @@ impl GameItem for Bullet {
     }
 
     fn kind(&self) -> GameItemKind {
-        GameItemKind::Bullet
+        self.kind.game_item_kind()
+    }
+
+    fn saved(&self) -> SavedGameItem {
+        SavedGameItem::Bullet(self.clone())
     }
 }
 
 impl Renderable for Bullet {
     fn render(&self, renderer: &mut Renderer) {
-        renderer.render_with_offset(self.viewport(), TEXT, ColorTheme::Bullet);
+        renderer.render_with_offset(self.viewport(), self.kind.text(), self.kind.color());
     }
 
     fn viewport(&self) -> Viewport {
@@ -55,24 +107,34 @@ impl Renderable for Bullet {
 }
 
 impl TickHandler for Bullet {
-    fn handle_tick(&mut self, ticker: &Ticker, world_viewport: Viewport) {
-        if ticker.at(Frequency::One) {
-            self.coordinates.offset_y(1);
-
-            if !world_viewport.intersects_vertically(self.viewport()) {
-                self.deleted = true;
-            }
+    fn handle_tick(&mut self, _: &Ticker, world_viewport: Viewport) -> Vec<Command> {
+        self.velocity.move_coordinates(&mut self.coordinates);
+        if !world_viewport.intersects_vertically(self.viewport()) {
+            self.deleted = true;
         }
+        NO_COMMANDS
     }
 }
 
 impl Bullet {
     pub fn new(coordinates: Coordinates) -> Self {
+        Self::new_with_kind(coordinates, BulletKind::Ship, 0)
+    }
+
+    // `dx` is the horizontal velocity in subcells per tick, which allows volleys to fan out.
+    pub fn new_enemy(coordinates: Coordinates, dx: i16) -> Self {
+        Self::new_with_kind(coordinates, BulletKind::Enemy, dx)
+    }
+
+    fn new_with_kind(coordinates: Coordinates, kind: BulletKind, dx: i16) -> Self {
+        let text = kind.text();
         Self {
             coordinates,
             deleted: false,
-            height: chars_height(TEXT),
-            width: chars_width(TEXT),
+            height: chars_height(text),
+            kind,
+            velocity: Velocity::new(dx, kind.dy()),
+            width: chars_width(text),
         }
     }
 }
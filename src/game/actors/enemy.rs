@@ -0,0 +1,234 @@
+use crate::{
+    app::{
+        color::ColorTheme,
+        command::{Command, CommandHandler, NO_COMMANDS},
+        main::TICKS_PER_SECOND,
+    },
+    clock::{
+        countdown::Countdown,
+        ticker::{Frequency, TickHandler, Ticker},
+    },
+    game::{
+        game_item::{GameItem, GameItemKind, SavedGameItem},
+        hitbox::Hitbox,
+        radar::{find_ship, Contact},
+    },
+    view::{
+        coordinates::Coordinates,
+        render::Renderable,
+        renderer::Renderer,
+        sprite::{self, Sprite},
+        util::{chars_height, chars_width},
+        velocity::{Velocity, SUBCELLS},
+        viewport::Viewport,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+const FIGHTER_AIM_WIDTH: i16 = 2; // Fire when the ship is within this many columns
+const HORIZONTAL_DX: i16 = SUBCELLS / 2; // One column every other tick
+const STRAFE_COUNT: u16 = TICKS_PER_SECOND * 2; // 2 seconds in each direction
+
+#[derive(Clone, Copy, Deserialize, Serialize)]
+enum EnemyKind {
+    Fighter, // Tracks the ship's x-position and fires when aligned with it
+    Strafer, // Moves side-to-side and fires continuously
+}
+
+impl EnemyKind {
+    fn color(self) -> ColorTheme {
+        match self {
+            Self::Fighter => ColorTheme::EnemyFighter,
+            Self::Strafer => ColorTheme::EnemyStrafer,
+        }
+    }
+
+    fn fire_count(self) -> u16 {
+        match self {
+            Self::Fighter => TICKS_PER_SECOND * 2,
+            Self::Strafer => TICKS_PER_SECOND * 3,
+        }
+    }
+
+    fn initial_hp(self) -> u8 {
+        match self {
+            Self::Fighter => 3,
+            Self::Strafer => 5,
+        }
+    }
+
+    fn points(self) -> u32 {
+        match self {
+            Self::Fighter => 15,
+            Self::Strafer => 20,
+        }
+    }
+
+    fn text(self) -> &'static str {
+        match self {
+            Self::Fighter => sprite::text(Sprite::EnemyFighter),
+            Self::Strafer => sprite::text(Sprite::EnemyStrafer),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Enemy {
+    coordinates: Coordinates,
+    deleted: bool,
+    direction: i16, // -1 or 1
+    disabled_guns: Countdown,
+    height: u8,
+    hp: u8,
+    kind: EnemyKind,
+    ship: Option<Viewport>,
+    strafe: Countdown,
+    velocity: Velocity,
+    width: u8,
+}
+
+impl CommandHandler for Enemy {
+    fn handle_command(&mut self, command: Command) -> Vec<Command> {
+        if let Command::Collide(kind, _) = command {
+            match kind {
+                GameItemKind::Bullet => self.hp = self.hp.saturating_sub(1),
+                GameItemKind::Missile | GameItemKind::Ship => self.hp = 0,
+                _ => (),
+            }
+            if self.hp == 0 {
+                self.deleted = true;
+                return vec![
+                    Command::AddExplosion(self.viewport().centered()),
+                    Command::IncreaseScore(self.kind.points()),
+                ];
+            }
+        }
+        NO_COMMANDS
+    }
+}
+
+impl GameItem for Enemy {
+    fn deleted(&self) -> bool {
+        self.deleted
+    }
+
+    fn handle_radar(&mut self, contacts: &[Contact]) {
+        self.ship = find_ship(contacts).map(|contact| contact.viewport);
+    }
+
+    fn hitboxes(&self) -> Vec<Hitbox> {
+        vec![Hitbox::new_with_mask(self.viewport(), self.kind.text())]
+    }
+
+    fn kind(&self) -> GameItemKind {
+        GameItemKind::Enemy
+    }
+
+    fn saved(&self) -> SavedGameItem {
+        SavedGameItem::Enemy(self.clone())
+    }
+}
+
+impl Renderable for Enemy {
+    fn render(&self, renderer: &mut Renderer) {
+        renderer.render_with_offset(self.viewport(), self.kind.text(), self.kind.color());
+    }
+
+    fn viewport(&self) -> Viewport {
+        Viewport::new_with_coordinates(self.width, self.height, self.coordinates)
+    }
+}
+
+impl TickHandler for Enemy {
+    fn handle_tick(&mut self, _: &Ticker, world_viewport: Viewport) -> Vec<Command> {
+        self.disabled_guns.down();
+        self.strafe.down();
+
+        self.steer();
+        self.velocity.move_coordinates(&mut self.coordinates);
+        if !world_viewport.intersects_vertically(self.viewport()) {
+            self.deleted = true;
+            return NO_COMMANDS;
+        }
+        if self.disabled_guns.off() && self.should_fire() {
+            self.disabled_guns.restart();
+            let mut coordinates = self.viewport().centered();
+            coordinates.offset_y(-i16::from(self.height));
+            return vec![Command::AddEnemyBullet(coordinates, 0)];
+        }
+        NO_COMMANDS
+    }
+}
+
+impl Enemy {
+    pub fn new_fighter(coordinates: Coordinates) -> Self {
+        Self::new(coordinates, EnemyKind::Fighter)
+    }
+
+    pub fn new_strafer(coordinates: Coordinates) -> Self {
+        Self::new(coordinates, EnemyKind::Strafer)
+    }
+
+    fn new(coordinates: Coordinates, kind: EnemyKind) -> Self {
+        let text = kind.text();
+        let mut disabled_guns = Countdown::new(kind.fire_count());
+        disabled_guns.restart(); // Don't fire immediately after spawning.
+        Self {
+            coordinates,
+            deleted: false,
+            direction: 1,
+            disabled_guns,
+            height: chars_height(text),
+            hp: kind.initial_hp(),
+            kind,
+            ship: None,
+            strafe: Countdown::new(STRAFE_COUNT),
+            velocity: Velocity::from_frequency((0, -1), Frequency::Five),
+            width: chars_width(text),
+        }
+    }
+
+    // Set the horizontal velocity: towards the ship, or side-to-side.
+    fn steer(&mut self) {
+        let direction = match self.kind {
+            EnemyKind::Fighter => self
+                .ship
+                .map_or(0, |ship| self.viewport().distance_x(ship).signum()),
+            EnemyKind::Strafer => {
+                if self.strafe.off() {
+                    self.strafe.restart();
+                    self.direction = -self.direction;
+                }
+                self.direction
+            }
+        };
+        self.velocity.steer(direction * HORIZONTAL_DX);
+    }
+
+    fn should_fire(&self) -> bool {
+        match self.kind {
+            EnemyKind::Fighter => self
+                .ship
+                .is_some_and(|ship| self.viewport().distance_x(ship).abs() <= FIGHTER_AIM_WIDTH),
+            EnemyKind::Strafer => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn strafers_move_a_column_every_other_tick_and_a_row_every_16_ticks() {
+        let mut strafer = Enemy::new_strafer(Coordinates::new(100, 30));
+        let ticker = Ticker::new(Duration::ZERO);
+        for _ in 0..u16::from(Frequency::Five) {
+            strafer.handle_tick(&ticker, Viewport::new_for_world());
+        }
+
+        // Strafers start by turning around.
+        assert_eq!(strafer.coordinates.as_tuple(), (92, 29));
+    }
+}
@@ -1,81 +1,30 @@
 use crate::{
-    app::{color::ColorTheme, command::CommandHandler, main::TICKS_PER_SECOND},
-    clock::{
-        countdown::Countdown,
-        ticker::{TickHandler, Ticker},
+    app::{
+        color::ColorTheme,
+        command::{Command, CommandHandler, NO_COMMANDS},
+        main::TICKS_PER_SECOND,
     },
-    game::game_item::GameItem,
+    clock::ticker::{TickHandler, Ticker},
+    game::game_item::{GameItem, SavedGameItem},
     view::{
+        animation::{Animation, Frame, Playback},
         coordinates::Coordinates,
         render::Renderable,
         renderer::Renderer,
+        sprite::Sprite,
         util::{chars_height, chars_width},
         viewport::Viewport,
     },
 };
+use serde::{Deserialize, Serialize};
 
-const ANIMATION_COUNT: u16 = TICKS_PER_SECOND / 10; // 100ms
-
-static TEXT_A: &str = "\
-\x20
-▒▒
-\x20";
-
-static TEXT_B: &str = "\
-\x20 ░░
- ░▒▒░
-  ░░";
-
-static TEXT_C_AND_D: &str = "\
-░▒▒▒░
-▒░░░▒
-░▒▒▒░";
-
-enum Animation {
-    A,
-    B,
-    C,
-    D,
-    Deleted,
-}
-
-impl Animation {
-    fn color(&self) -> ColorTheme {
-        match self {
-            Self::A => ColorTheme::ExplosionA,
-            Self::B => ColorTheme::ExplosionB,
-            Self::C => ColorTheme::ExplosionC,
-            Self::D => ColorTheme::ExplosionD,
-            Self::Deleted => panic!("Cannot invoke methods on Lifecycle::Deleted"),
-        }
-    }
-
-    fn next(&self) -> Self {
-        match self {
-            Self::A => Self::B,
-            Self::B => Self::C,
-            Self::C => Self::D,
-            Self::D => Self::Deleted,
-            Self::Deleted => panic!("Cannot advance past Lifecycle::Deleted"),
-        }
-    }
-
-    fn text(&self) -> &'static str {
-        match self {
-            Self::A => TEXT_A,
-            Self::B => TEXT_B,
-            Self::C => TEXT_C_AND_D,
-            Self::D => TEXT_C_AND_D,
-            Self::Deleted => panic!("Cannot invoke methods on Lifecycle::Deleted"),
-        }
-    }
-}
+const FRAME_TICKS: u16 = TICKS_PER_SECOND / 10; // 100ms
 
+#[derive(Clone, Deserialize, Serialize)]
 pub struct Explosion {
     coordinates: Coordinates,
     deleted: bool,
     animation: Animation,
-    animation_next: Countdown,
 }
 
 impl CommandHandler for Explosion {}
@@ -84,6 +33,10 @@ impl GameItem for Explosion {
     fn deleted(&self) -> bool {
         self.deleted
     }
+
+    fn saved(&self) -> SavedGameItem {
+        SavedGameItem::Explosion(self.clone())
+    }
 }
 
 impl Renderable for Explosion {
@@ -99,28 +52,25 @@ impl Renderable for Explosion {
 }
 
 impl TickHandler for Explosion {
-    fn handle_tick(&mut self, _: &Ticker, _: Viewport) {
-        if self.animation_next.off() {
-            self.animation_next.restart();
-            self.animation = self.animation.next();
-            if let Animation::Deleted = self.animation {
-                self.deleted = true;
-            }
-        }
-
-        self.animation_next.down();
+    fn handle_tick(&mut self, _: &Ticker, _: Viewport) -> Vec<Command> {
+        self.animation.handle_tick(|| self.deleted = true);
+        NO_COMMANDS
     }
 }
 
 impl Explosion {
     pub fn new(coordinates: Coordinates) -> Self {
-        let mut animation_next = Countdown::new(ANIMATION_COUNT);
-        animation_next.restart();
+        // The last frame is shown twice, in two colors.
+        let frames = vec![
+            Frame::new(0, ColorTheme::ExplosionA, FRAME_TICKS),
+            Frame::new(1, ColorTheme::ExplosionB, FRAME_TICKS),
+            Frame::new(2, ColorTheme::ExplosionC, FRAME_TICKS),
+            Frame::new(2, ColorTheme::ExplosionD, FRAME_TICKS),
+        ];
         let mut explosion = Self {
             coordinates,
             deleted: false,
-            animation: Animation::A,
-            animation_next,
+            animation: Animation::new(Sprite::Explosion, Playback::Once, frames),
         };
         explosion.coordinates = explosion.viewport().centered_around_bottom_left();
         explosion
@@ -0,0 +1,195 @@
+use crate::{
+    app::{
+        color::ColorTheme,
+        command::{Command, CommandHandler, CommandKind, NO_COMMANDS},
+    },
+    clock::ticker::TickHandler,
+    game::{
+        game_item::{GameItem, SavedGameItem},
+        high_scores::{HighScore, HighScores, Initials, INITIALS_LENGTH},
+    },
+    view::{
+        coordinates::Coordinates, render::Renderable, renderer::Renderer, util::chars_width,
+        viewport::Viewport,
+    },
+};
+use serde::{Deserialize, Serialize};
+use tui::{
+    style::{Modifier, Style},
+    text::Span,
+};
+
+static TEXT_HEADER: &str = "High scores";
+static TEXT_PROMPT: &str = "New high score! Type your initials and press [Enter]";
+const PLACEHOLDER: char = '_';
+
+// Displays the high scores in the middle of the visible actors viewport when the player has achieved
+// a new high score, and prompts for their initials. It's removed once the initials are submitted, so
+// that the game over menu can be shown.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct HighScoreTable {
+    deleted: bool,
+    initials: Vec<char>,
+    rank: Option<usize>, // The rank of the new high score, while initials are being entered
+    scores: Vec<HighScore>,
+}
+
+impl CommandHandler for HighScoreTable {
+    fn handle_command(&mut self, command: Command) -> Vec<Command> {
+        let Some(rank) = self.rank else {
+            return NO_COMMANDS;
+        };
+        match command {
+            Command::DeleteInitial => {
+                self.initials.pop();
+            }
+            Command::EnterInitial(char) if self.initials.len() < INITIALS_LENGTH => {
+                self.initials.push(char);
+            }
+            Command::SubmitInitials if !self.initials.is_empty() => {
+                let mut initials: Initials = [' '; INITIALS_LENGTH];
+                initials[..self.initials.len()].copy_from_slice(&self.initials);
+                self.scores[rank].initials = self.initials.iter().collect();
+                self.deleted = true;
+                self.rank = None;
+                return vec![Command::SaveHighScore(initials)];
+            }
+            _ => (),
+        }
+        NO_COMMANDS
+    }
+}
+
+impl GameItem for HighScoreTable {
+    fn deleted(&self) -> bool {
+        self.deleted
+    }
+
+    fn saved(&self) -> SavedGameItem {
+        SavedGameItem::HighScoreTable(self.clone())
+    }
+
+    fn subscriptions(&self) -> &'static [CommandKind] {
+        &[
+            CommandKind::DeleteInitial,
+            CommandKind::EnterInitial,
+            CommandKind::SubmitInitials,
+        ]
+    }
+}
+
+impl Renderable for HighScoreTable {
+    fn render(&self, renderer: &mut Renderer) {
+        // Render without the world offset, so that the table stays still.
+        let lines = self.lines();
+        let (x, y) = renderer.viewport().centered().as_tuple();
+        let height = i8::try_from(lines.len()).unwrap();
+        let top = y.saturating_add(height / 2);
+        for (i, (text, color)) in lines.into_iter().enumerate() {
+            let width = chars_width(text.as_str());
+            let x = x.saturating_sub(width / 2);
+            let y = top - i8::try_from(i).unwrap();
+            let viewport = Viewport::new_with_coordinates(width, 1, Coordinates::new(x, y));
+            let mut style = Style::from(color);
+            if let ColorTheme::HighScoresHeader | ColorTheme::HighScoresNew = color {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            renderer.render_spans(viewport, vec![Span::styled(text, style)]);
+        }
+    }
+
+    fn viewport(&self) -> Viewport {
+        Viewport::new(chars_width(TEXT_PROMPT), 1)
+    }
+}
+
+impl TickHandler for HighScoreTable {}
+
+impl HighScoreTable {
+    // If `new_score` qualifies, then it's shown in the table while initials are being entered.
+    pub fn new(high_scores: &HighScores, new_score: HighScore) -> Self {
+        let mut preview = high_scores.clone();
+        let rank = preview.insert(new_score);
+        Self {
+            deleted: false,
+            initials: Vec::new(),
+            rank,
+            scores: preview.scores().to_vec(),
+        }
+    }
+
+    fn lines(&self) -> Vec<(String, ColorTheme)> {
+        let mut lines = vec![(TEXT_HEADER.to_string(), ColorTheme::HighScoresHeader)];
+        if self.scores.is_empty() {
+            lines.push((String::from("None yet"), ColorTheme::HighScoresRow));
+        }
+        for (i, high_score) in self.scores.iter().enumerate() {
+            if Some(i) == self.rank {
+                lines.push((
+                    high_score.row(i, &self.text_initials()),
+                    ColorTheme::HighScoresNew,
+                ));
+            } else {
+                lines.push((
+                    high_score.row(i, &high_score.initials),
+                    ColorTheme::HighScoresRow,
+                ));
+            }
+        }
+        if self.rank.is_some() {
+            lines.push((TEXT_PROMPT.to_string(), ColorTheme::HighScoresNew));
+        }
+        lines
+    }
+
+    fn text_initials(&self) -> String {
+        let mut initials: String = self.initials.iter().collect();
+        while initials.chars().count() < INITIALS_LENGTH {
+            initials.push(PLACEHOLDER);
+        }
+        initials
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_score(score: u32) -> HighScore {
+        HighScore {
+            date: String::from("2024-01-01"),
+            initials: String::new(),
+            level: 2,
+            score,
+            seed: 7,
+        }
+    }
+
+    #[test]
+    fn submits_the_entered_initials() {
+        let mut table = HighScoreTable::new(&HighScores::default(), new_score(10));
+        table.handle_command(Command::EnterInitial('A'));
+        table.handle_command(Command::EnterInitial('X'));
+        table.handle_command(Command::DeleteInitial);
+        table.handle_command(Command::EnterInitial('B'));
+
+        let commands = table.handle_command(Command::SubmitInitials);
+
+        assert!(matches!(
+            commands[..],
+            [Command::SaveHighScore(['A', 'B', ' '])]
+        ));
+        assert!(table.handle_command(Command::SubmitInitials).is_empty());
+        assert!(table.lines()[1].0.contains("AB "));
+        assert!(table.deleted());
+    }
+
+    #[test]
+    fn ignores_initials_when_the_score_does_not_qualify() {
+        let mut table = HighScoreTable::new(&HighScores::default(), new_score(0));
+        table.handle_command(Command::EnterInitial('A'));
+
+        assert!(table.handle_command(Command::SubmitInitials).is_empty());
+        assert_eq!(table.lines().len(), 2); // The header and "None yet"
+    }
+}
@@ -0,0 +1,86 @@
+use crate::{
+    app::{
+        color::ColorTheme,
+        command::{Command, CommandHandler, NO_COMMANDS},
+        main::TICKS_PER_SECOND,
+    },
+    clock::{
+        countdown::Countdown,
+        ticker::{TickHandler, Ticker},
+    },
+    game::game_item::{GameItem, SavedGameItem},
+    view::{
+        coordinates::Coordinates, render::Renderable, renderer::Renderer, util::chars_width,
+        viewport::Viewport,
+    },
+};
+use serde::{Deserialize, Serialize};
+use tui::{
+    style::{Modifier, Style},
+    text::Span,
+};
+
+const VISIBLE_COUNT: u16 = TICKS_PER_SECOND * 2; // 2 seconds
+
+// Announces the start of a level in the middle of the visible actors viewport.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct LevelBanner {
+    deleted: bool,
+    level: u8,
+    visible: Countdown,
+}
+
+impl CommandHandler for LevelBanner {}
+
+impl GameItem for LevelBanner {
+    fn deleted(&self) -> bool {
+        self.deleted
+    }
+
+    fn saved(&self) -> SavedGameItem {
+        SavedGameItem::LevelBanner(self.clone())
+    }
+}
+
+impl Renderable for LevelBanner {
+    fn render(&self, renderer: &mut Renderer) {
+        // Render without the world offset, so that the banner stays still when the ship moves.
+        let (x, y) = renderer.viewport().centered().as_tuple();
+        let text = self.text();
+        let width = chars_width(text.as_str());
+        let x = x.saturating_sub(width / 2);
+        let viewport = self.viewport().with_coordinates(Coordinates::new(x, y));
+        let style = Style::from(ColorTheme::LevelBanner).add_modifier(Modifier::BOLD);
+        renderer.render_spans(viewport, vec![Span::styled(text, style)]);
+    }
+
+    fn viewport(&self) -> Viewport {
+        Viewport::new(chars_width(self.text().as_str()), 1)
+    }
+}
+
+impl TickHandler for LevelBanner {
+    fn handle_tick(&mut self, _: &Ticker, _: Viewport) -> Vec<Command> {
+        self.visible.down();
+        if self.visible.off() {
+            self.deleted = true;
+        }
+        NO_COMMANDS
+    }
+}
+
+impl LevelBanner {
+    pub fn new(level: u8) -> Self {
+        let mut visible = Countdown::new(VISIBLE_COUNT);
+        visible.restart();
+        Self {
+            deleted: false,
+            level,
+            visible,
+        }
+    }
+
+    fn text(&self) -> String {
+        format!("Level {}", self.level)
+    }
+}
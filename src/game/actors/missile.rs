@@ -4,31 +4,38 @@ use crate::{
         command::{Command, CommandHandler, NO_COMMANDS},
     },
     clock::ticker::{Frequency, TickHandler, Ticker},
-    game::game_item::{GameItem, GameItemKind},
+    game::{
+        game_item::{GameItem, GameItemKind, SavedGameItem},
+        hitbox::Hitbox,
+        radar::{find_nearest_target_above, Contact},
+    },
     view::{
         coordinates::Coordinates,
         render::Renderable,
         renderer::Renderer,
+        sprite::{self, Sprite},
         util::{chars_height, chars_width},
+        velocity::{Velocity, SUBCELLS},
         viewport::Viewport,
     },
 };
+use serde::{Deserialize, Serialize};
 
-static TEXT: &str = "\
-\x20 ▄ \x20
-▟███▙
-▀▜ ▛▀";
+const STEERING_DX: i16 = SUBCELLS / 2; // Horizontal velocity while steering towards a target
 
+#[derive(Clone, Deserialize, Serialize)]
 pub struct Missile {
     coordinates: Coordinates,
     deleted: bool,
     height: u8,
+    target: Option<Viewport>,
+    velocity: Velocity,
     width: u8,
 }
 
 impl CommandHandler for Missile {
     fn handle_command(&mut self, command: Command) -> Vec<Command> {
-        if let Command::Collide(kind) = command {
+        if let Command::Collide(kind, _) = command {
             if kind.is_shootable() {
                 self.deleted = true
             }
@@ -42,14 +49,35 @@ impl GameItem for Missile {
         self.deleted
     }
 
+    // Re-target on every tick, so that another target is acquired when the current one is destroyed.
+    fn handle_radar(&mut self, contacts: &[Contact]) {
+        self.target =
+            find_nearest_target_above(contacts, self.viewport()).map(|contact| contact.viewport);
+    }
+
+    fn hitboxes(&self) -> Vec<Hitbox> {
+        vec![Hitbox::new_with_mask(
+            self.viewport(),
+            sprite::text(Sprite::Missile),
+        )]
+    }
+
     fn kind(&self) -> GameItemKind {
         GameItemKind::Missile
     }
+
+    fn saved(&self) -> SavedGameItem {
+        SavedGameItem::Missile(self.clone())
+    }
 }
 
 impl Renderable for Missile {
     fn render<'a>(&self, renderer: &mut Renderer) {
-        renderer.render_with_offset(self.viewport(), TEXT, ColorTheme::Missile);
+        renderer.render_with_offset(
+            self.viewport(),
+            sprite::text(Sprite::Missile),
+            ColorTheme::Missile,
+        );
     }
 
     fn viewport(&self) -> Viewport {
@@ -58,28 +86,56 @@ impl Renderable for Missile {
 }
 
 impl TickHandler for Missile {
-    fn handle_tick(&mut self, ticker: &Ticker, world_viewport: Viewport) {
-        if ticker.at(Frequency::Two) {
-            self.coordinates.offset_y(1);
-
-            if !world_viewport.intersects_vertically(self.viewport()) {
-                self.deleted = true;
-            }
+    fn handle_tick(&mut self, _: &Ticker, world_viewport: Viewport) -> Vec<Command> {
+        let dx = self
+            .target
+            .map_or(0, |target| self.viewport().distance_x(target).signum());
+        self.velocity.steer(dx * STEERING_DX);
+        self.velocity.move_coordinates(&mut self.coordinates);
+        if !world_viewport.intersects_vertically(self.viewport()) {
+            self.deleted = true;
         }
+        NO_COMMANDS
     }
 }
 
 impl Missile {
     pub fn new(mut coordinates: Coordinates) -> Self {
         // The given coordinates are relative to the center of a ship, so left-align.
-        let height = chars_height(TEXT);
-        let width = chars_width(TEXT);
+        let height = chars_height(sprite::text(Sprite::Missile));
+        let width = chars_width(sprite::text(Sprite::Missile));
         coordinates.offset_x(i16::from(width) / -2);
         Self {
             coordinates,
             deleted: false,
             height,
+            target: None,
+            velocity: Velocity::from_frequency((0, 1), Frequency::Two),
             width,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn steers_towards_the_nearest_target_above() {
+        let mut missile = Missile::new(Coordinates::new(50, 0));
+        let target = Contact {
+            kind: GameItemKind::Asteroid,
+            viewport: Viewport::new_with_coordinates(1, 1, Coordinates::new(40, 20)),
+        };
+        missile.handle_radar(&[target]);
+        let ticker = Ticker::new(Duration::ZERO);
+        for _ in 0..4 {
+            missile.handle_tick(&ticker, Viewport::new_for_world());
+        }
+
+        let (x, y) = missile.coordinates.as_tuple();
+        assert!(x < 48);
+        assert_eq!(y, 2);
+    }
+}
@@ -2,33 +2,44 @@ use crate::{
     app::{
         color::ColorTheme,
         command::{Command, CommandHandler, NO_COMMANDS},
+        main::TICKS_PER_SECOND,
     },
     clock::ticker::{Frequency, TickHandler, Ticker},
-    game::game_item::{GameItem, GameItemKind},
+    game::{
+        game_item::{GameItem, GameItemKind, SavedGameItem},
+        hitbox::Hitbox,
+    },
     view::{
+        animation::{Animation, Frame, Playback},
         coordinates::Coordinates,
         render::Renderable,
         renderer::Renderer,
+        sprite::{self, Sprite},
         util::{chars_height, chars_width},
+        velocity::Velocity,
         viewport::Viewport,
     },
 };
+use serde::{Deserialize, Serialize};
 
-static TEXT_HEALTH: &str = "\
-┏━━━┓
-┃ h ┃
-┗━━━┛";
-static TEXT_MISSILE: &str = "\
-┏━━━┓
-┃ m ┃
-┗━━━┛";
+const BLINK_TICKS: u16 = TICKS_PER_SECOND / 5; // 200ms
 
+#[derive(Clone, Deserialize, Serialize)]
 enum PowerUpKind {
     Health,
     Missile,
 }
 
 impl PowerUpKind {
+    // Blink, to stand out from the actors that should be avoided.
+    fn animation(&self) -> Animation {
+        let frames = vec![
+            Frame::new(0, self.color(), BLINK_TICKS * 3),
+            Frame::new(0, ColorTheme::PowerUpBlink, BLINK_TICKS),
+        ];
+        Animation::new(self.sprite(), Playback::Loop, frames)
+    }
+
     fn color(&self) -> ColorTheme {
         match self {
             PowerUpKind::Health => ColorTheme::PowerUpHealth,
@@ -36,27 +47,32 @@ impl PowerUpKind {
         }
     }
 
-    fn text(&self) -> &'static str {
+    fn sprite(&self) -> Sprite {
         match self {
-            PowerUpKind::Health => TEXT_HEALTH,
-            PowerUpKind::Missile => TEXT_MISSILE,
+            PowerUpKind::Health => Sprite::PowerUpHealth,
+            PowerUpKind::Missile => Sprite::PowerUpMissile,
         }
     }
+
+    fn text(&self) -> &'static str {
+        sprite::text(self.sprite())
+    }
 }
 
+#[derive(Clone, Deserialize, Serialize)]
 pub struct PowerUp {
-    color: ColorTheme,
+    animation: Animation,
     coordinates: Coordinates,
     deleted: bool,
     height: u8,
     kind: PowerUpKind,
-    text: &'static str,
+    velocity: Velocity,
     width: u8,
 }
 
 impl CommandHandler for PowerUp {
     fn handle_command(&mut self, command: Command) -> Vec<Command> {
-        if let Command::Collide(GameItemKind::Ship | GameItemKind::ShipWithShields) = command {
+        if let Command::Collide(GameItemKind::Ship | GameItemKind::ShipWithShields, _) = command {
             self.deleted = true;
             return match self.kind {
                 PowerUpKind::Health => vec![Command::IncreaseHealth(1)],
@@ -71,11 +87,24 @@ impl GameItem for PowerUp {
     fn deleted(&self) -> bool {
         self.deleted
     }
+
+    fn hitboxes(&self) -> Vec<Hitbox> {
+        vec![Hitbox::new_with_mask(self.viewport(), self.kind.text())]
+    }
+
+    fn kind(&self) -> GameItemKind {
+        GameItemKind::PowerUp
+    }
+
+    fn saved(&self) -> SavedGameItem {
+        SavedGameItem::PowerUp(self.clone())
+    }
 }
 
 impl Renderable for PowerUp {
     fn render(&self, renderer: &mut Renderer) {
-        renderer.render_with_offset(self.viewport(), self.text, self.color);
+        let text = self.animation.text();
+        renderer.render_with_offset(self.viewport(), text, self.animation.color());
     }
 
     fn viewport(&self) -> Viewport {
@@ -84,14 +113,13 @@ impl Renderable for PowerUp {
 }
 
 impl TickHandler for PowerUp {
-    fn handle_tick(&mut self, ticker: &Ticker, world_viewport: Viewport) {
-        if ticker.at(Frequency::Three) {
-            self.coordinates.offset_y(-1);
-
-            if !world_viewport.intersects_vertically(self.viewport()) {
-                self.deleted = true;
-            }
+    fn handle_tick(&mut self, _: &Ticker, world_viewport: Viewport) -> Vec<Command> {
+        self.animation.handle_tick(|| ());
+        self.velocity.move_coordinates(&mut self.coordinates);
+        if !world_viewport.intersects_vertically(self.viewport()) {
+            self.deleted = true;
         }
+        NO_COMMANDS
     }
 }
 
@@ -107,12 +135,12 @@ impl PowerUp {
     fn new(coordinates: Coordinates, kind: PowerUpKind) -> Self {
         let text = kind.text();
         Self {
-            color: kind.color(),
+            animation: kind.animation(),
             coordinates,
             deleted: false,
             height: chars_height(text),
             kind,
-            text,
+            velocity: Velocity::from_frequency((0, -1), Frequency::Three),
             width: chars_width(text),
         }
     }
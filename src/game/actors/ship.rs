@@ -1,53 +1,65 @@
 use crate::{
     app::{
         color::ColorTheme,
-        command::{Command, CommandHandler, NO_COMMANDS},
+        command::{Command, CommandHandler, CommandKind, NO_COMMANDS},
         main::TICKS_PER_SECOND,
     },
     clock::{
         countdown::Countdown,
         ticker::{TickHandler, Ticker},
     },
-    game::game_item::{GameItem, GameItemKind},
+    game::{
+        game_item::{GameItem, GameItemKind, SavedGameItem},
+        hitbox::Hitbox,
+    },
     view::{
+        animation::{Animation, Frame, Playback},
         coordinates::Coordinates,
         render::Renderable,
         renderer::Renderer,
+        sprite::{self, Sprite},
         util::{chars_height, chars_width},
         viewport::Viewport,
     },
 };
+use serde::{Deserialize, Serialize};
 
-static TEXT: &str = "◄◆►";
-
-static TEXT_SHIELDS: &str = "\
-░░░░░
-░◄◆►░
-░░░░░";
-
+const CONTACT_COOLDOWN_COUNT: u16 = TICKS_PER_SECOND; // 1 second
 const DISABLED_GUNS_COUNT: u16 = TICKS_PER_SECOND / 20; // 50 ms
 const ENABLED_SHIELDS_COUNT: u16 = TICKS_PER_SECOND * 5; // 5 seconds
 const INITIAL_MAX_HEALTH: u8 = 5;
 const INITIAL_MAX_MISSILES: u8 = 5;
 
+#[derive(Clone, Deserialize, Serialize)]
 pub struct Ship {
+    contact_cooldown: Countdown, // Until touching an enemy can cost health again
     coordinates: Coordinates,
     deleted: bool,
     disabled_guns: Countdown,
     enabled_shields: Countdown,
+    engine: Animation, // Flickers, except behind the shields
     health: u8,
     max_y: i8,
     missiles: u8,
 }
 
 impl CommandHandler for Ship {
+    #[allow(clippy::collapsible_match)]
     fn handle_command(&mut self, command: Command) -> Vec<Command> {
         match command {
-            Command::Collide(kind) => {
+            Command::Collide(kind, _) => {
                 if self.enabled_shields.on() {
                     return NO_COMMANDS;
                 }
-                if let GameItemKind::Asteroid = kind {
+                if kind.damages_ship() {
+                    // Enemies are destroyed by touching the ship, but the boss isn't, and each
+                    // collision pass while they overlap would otherwise cost health.
+                    if kind == GameItemKind::Enemy {
+                        if self.contact_cooldown.on() {
+                            return NO_COMMANDS;
+                        }
+                        self.contact_cooldown.restart();
+                    }
                     self.health = self.health.saturating_sub(1);
                     let mut commands = vec![Command::UpdateHealth(self.health, INITIAL_MAX_HEALTH)];
                     if self.health == 0 {
@@ -76,8 +88,8 @@ impl CommandHandler for Ship {
                 ];
             }
             Command::FireShields => {
-                let wider_width = chars_width(TEXT_SHIELDS);
-                let taller_height = chars_height(TEXT_SHIELDS);
+                let wider_width = chars_width(sprite::text(Sprite::ShipShields));
+                let taller_height = chars_height(sprite::text(Sprite::ShipShields));
                 self.coordinates = self.viewport().expanded(wider_width, taller_height);
                 self.enabled_shields.restart();
             }
@@ -130,6 +142,20 @@ impl GameItem for Ship {
         self.deleted
     }
 
+    fn health(&self) -> Option<u8> {
+        Some(self.health)
+    }
+
+    fn hitboxes(&self) -> Vec<Hitbox> {
+        let hitbox = Hitbox::new_with_mask(self.viewport(), self.text());
+        // The shields are drawn as a rectangle, but they're round, so their corners don't collide.
+        if self.enabled_shields.on() {
+            vec![hitbox.with_rounded_corners()]
+        } else {
+            vec![hitbox]
+        }
+    }
+
     fn kind(&self) -> GameItemKind {
         if self.enabled_shields.on() {
             GameItemKind::ShipWithShields
@@ -137,6 +163,23 @@ impl GameItem for Ship {
             GameItemKind::Ship
         }
     }
+
+    fn saved(&self) -> SavedGameItem {
+        SavedGameItem::Ship(self.clone())
+    }
+
+    fn subscriptions(&self) -> &'static [CommandKind] {
+        &[
+            CommandKind::ActorsViewportChanged,
+            CommandKind::ActorsViewportInitialized,
+            CommandKind::FireGuns,
+            CommandKind::FireMissile,
+            CommandKind::FireShields,
+            CommandKind::IncreaseHealth,
+            CommandKind::IncreaseMissiles,
+            CommandKind::MoveShip,
+        ]
+    }
 }
 
 impl Renderable for Ship {
@@ -150,27 +193,32 @@ impl Renderable for Ship {
 }
 
 impl TickHandler for Ship {
-    fn handle_tick(&mut self, _: &Ticker, _: Viewport) {
+    fn handle_tick(&mut self, _: &Ticker, _: Viewport) -> Vec<Command> {
+        self.contact_cooldown.down();
         self.disabled_guns.down();
+        self.engine.handle_tick(|| ());
 
         if self.enabled_shields.current() == 1 {
-            let narrower_width = chars_width(TEXT);
-            let shorter_height = chars_height(TEXT);
+            let narrower_width = chars_width(sprite::text(Sprite::Ship));
+            let shorter_height = chars_height(sprite::text(Sprite::Ship));
             // Shrink before turning off the shields, because turning off the shields will reduce
             // the ship's width, which will cause the `shrink()` calculation to be incorrect.
             self.coordinates = self.viewport().shrunk(narrower_width, shorter_height);
         }
         self.enabled_shields.down();
+        NO_COMMANDS
     }
 }
 
 impl Ship {
     pub fn new(coordinates: Coordinates) -> Self {
         Self {
+            contact_cooldown: Countdown::new(CONTACT_COOLDOWN_COUNT),
             coordinates,
             deleted: false,
             disabled_guns: Countdown::new(DISABLED_GUNS_COUNT),
             enabled_shields: Countdown::new(ENABLED_SHIELDS_COUNT),
+            engine: engine_animation(),
             health: INITIAL_MAX_HEALTH,
             max_y: 0,
             missiles: INITIAL_MAX_MISSILES,
@@ -181,15 +229,15 @@ impl Ship {
         if self.enabled_shields.on() {
             ColorTheme::ShipShields
         } else {
-            ColorTheme::Ship
+            self.engine.color()
         }
     }
 
     fn text(&self) -> &'static str {
         if self.enabled_shields.on() {
-            TEXT_SHIELDS
+            sprite::text(Sprite::ShipShields)
         } else {
-            TEXT
+            self.engine.text()
         }
     }
 
@@ -207,3 +255,44 @@ impl Ship {
         self.max_y = max_y;
     }
 }
+
+// An irregular flicker between the ship's two frames.
+fn engine_animation() -> Animation {
+    let frames = [(0, 3), (1, 1), (0, 1), (1, 2)]
+        .into_iter()
+        .map(|(index, ticks)| Frame::new(index, ColorTheme::Ship, ticks))
+        .collect();
+    Animation::new(Sprite::Ship, Playback::Loop, frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    // Collisions are checked after every tick, and after every input, so a held key causes many
+    // collision passes per tick.
+    #[test]
+    fn continuous_contact_with_an_enemy_costs_1_health_per_cooldown() {
+        let mut ship = Ship::new(Coordinates::new(50, 10));
+        let ticker = Ticker::new(Duration::ZERO);
+        for _ in 0..CONTACT_COOLDOWN_COUNT * 2 {
+            for _ in 0..10 {
+                ship.handle_command(Command::Collide(GameItemKind::Enemy, 0));
+            }
+            ship.handle_tick(&ticker, Viewport::new_for_world());
+        }
+
+        assert_eq!(ship.health, INITIAL_MAX_HEALTH - 2);
+    }
+
+    #[test]
+    fn enemy_bullets_damage_the_ship_during_the_contact_cooldown() {
+        let mut ship = Ship::new(Coordinates::new(50, 10));
+
+        ship.handle_command(Command::Collide(GameItemKind::Enemy, 0));
+        ship.handle_command(Command::Collide(GameItemKind::EnemyBullet, 0));
+
+        assert_eq!(ship.health, INITIAL_MAX_HEALTH - 2);
+    }
+}
@@ -0,0 +1,250 @@
+// Commands are delivered as events, which are either broadcast to the World and to every item that
+// subscribes to their kind, or addressed to a single item, such as each of the actors in a collision.
+use super::game_item::GameItem;
+use crate::app::command::Command;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+// An event that is caused by a chain of this many events is assumed to be part of a cycle, even if
+// its command differs each time, such as a score that keeps increasing.
+const MAX_CHAIN_LENGTH: usize = 16;
+
+// Identifies an item for as long as it exists. IDs are never re-used within a World.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ItemId(u32);
+
+impl ItemId {
+    pub fn next(self) -> Self {
+        Self(self.0 + 1)
+    }
+}
+
+impl fmt::Display for ItemId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+// A game item, with the ID that events can be addressed to.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Item {
+    pub id: ItemId,
+    #[serde(flatten)]
+    item: Box<dyn GameItem>,
+}
+
+impl Item {
+    pub fn new(id: ItemId, item: Box<dyn GameItem>) -> Self {
+        Self { id, item }
+    }
+}
+
+impl Deref for Item {
+    type Target = dyn GameItem;
+
+    fn deref(&self) -> &Self::Target {
+        self.item.as_ref()
+    }
+}
+
+impl DerefMut for Item {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.item.as_mut()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Target {
+    All, // The World, and the items that subscribe to the command's kind
+    Item(ItemId),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Event {
+    pub command: Command,
+    pub source: Option<ItemId>, // `None` for commands from the player, the World, or the Spawner
+    pub target: Target,
+}
+
+impl Event {
+    pub fn broadcast(command: Command) -> Self {
+        Self {
+            command,
+            source: None,
+            target: Target::All,
+        }
+    }
+
+    // A command that an item returned, which is broadcast.
+    pub fn published_by(source: ItemId, command: Command) -> Self {
+        Self {
+            command,
+            source: Some(source),
+            target: Target::All,
+        }
+    }
+
+    pub fn addressed(source: ItemId, target: ItemId, command: Command) -> Self {
+        Self {
+            command,
+            source: Some(source),
+            target: Target::Item(target),
+        }
+    }
+}
+
+// E.g. `Collide(Bullet, 0) to #12 from #40`.
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.command)?;
+        if let Target::Item(target) = self.target {
+            write!(f, " to {target}")?;
+        }
+        if let Some(source) = self.source {
+            write!(f, " from {source}")?;
+        }
+        Ok(())
+    }
+}
+
+// A queue of events, which are delivered in the order that they were published. Each event
+// remembers the event that caused it, so that cycles can be detected.
+#[derive(Default)]
+pub struct EventBus {
+    events: Vec<(Event, Option<usize>)>, // Each event, and the index of its cause
+    queue: VecDeque<usize>,
+}
+
+impl EventBus {
+    pub fn publish(&mut self, event: Event) {
+        self.push(event, None);
+    }
+
+    // Queue an event that was published in response to the event at `cause`, unless it would
+    // deliver the same command to the same target as one of the events that caused it.
+    pub fn publish_caused(&mut self, cause: usize, event: Event) -> Result<()> {
+        let chain = self.chain(cause);
+        let repeated = chain
+            .iter()
+            .any(|other| other.command == event.command && other.target == event.target);
+        if repeated || chain.len() >= MAX_CHAIN_LENGTH {
+            let chain: Vec<String> = chain
+                .iter()
+                .rev()
+                .chain([&event])
+                .map(Event::to_string)
+                .collect();
+            return Err(anyhow!("dropped a cycle of events: {}", chain.join(" -> ")));
+        }
+        self.push(event, Some(cause));
+        Ok(())
+    }
+
+    // The next event to deliver, and its index, which is the cause of any events it leads to.
+    pub fn next(&mut self) -> Option<(usize, Event)> {
+        let index = self.queue.pop_front()?;
+        Some((index, self.events[index].0))
+    }
+
+    // The event at `index`, followed by the events that caused it.
+    fn chain(&self, index: usize) -> Vec<Event> {
+        let mut chain = Vec::new();
+        let mut next = Some(index);
+        while let Some(index) = next {
+            let (event, cause) = self.events[index];
+            chain.push(event);
+            next = cause;
+        }
+        chain
+    }
+
+    fn push(&mut self, event: Event, cause: Option<usize>) {
+        self.queue.push_back(self.events.len());
+        self.events.push((event, cause));
+    }
+}
+
+// Problems that don't stop the game, such as events that nothing handled. They are reported when
+// the application exits in debug builds, because the terminal can't be written to while the TUI is
+// active.
+#[derive(Default)]
+pub struct Diagnostics {
+    messages: Vec<(String, u32)>, // Each distinct message, and how many times it was recorded
+}
+
+impl Diagnostics {
+    pub fn record(&mut self, message: String) {
+        match self
+            .messages
+            .iter_mut()
+            .find(|(other, _)| *other == message)
+        {
+            Some((_, count)) => *count += 1,
+            None => self.messages.push((message, 1)),
+        }
+    }
+
+    pub fn report(&self) -> Vec<String> {
+        self.messages
+            .iter()
+            .map(|(message, count)| match count {
+                1 => message.clone(),
+                _ => format!("{message} ({count} times)"),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_are_delivered_in_the_order_they_were_published() {
+        let mut bus = EventBus::default();
+        bus.publish(Event::broadcast(Command::FireGuns));
+        let (cause, _) = bus.next().unwrap();
+        bus.publish_caused(cause, Event::broadcast(Command::Rewind))
+            .unwrap();
+        bus.publish_caused(cause, Event::broadcast(Command::GameOver))
+            .unwrap();
+
+        assert_eq!(bus.next().unwrap().1.command, Command::Rewind);
+        assert_eq!(bus.next().unwrap().1.command, Command::GameOver);
+        assert!(bus.next().is_none());
+    }
+
+    #[test]
+    fn publish_caused_rejects_an_event_that_repeats_its_cause() {
+        let mut bus = EventBus::default();
+        let target = ItemId::default();
+        let event = Event::addressed(target.next(), target, Command::FireGuns);
+        bus.publish(event);
+        let (first, _) = bus.next().unwrap();
+        bus.publish_caused(first, Event::broadcast(Command::GameOver))
+            .unwrap();
+        let (second, _) = bus.next().unwrap();
+
+        let message = bus.publish_caused(second, event).unwrap_err().to_string();
+        assert_eq!(
+            message,
+            "dropped a cycle of events: FireGuns to #0 from #1 -> GameOver -> FireGuns to #0 from #1"
+        );
+        assert!(bus.next().is_none());
+    }
+
+    #[test]
+    fn report_counts_repeated_messages() {
+        let mut diagnostics = Diagnostics::default();
+        diagnostics.record(String::from("a"));
+        diagnostics.record(String::from("b"));
+        diagnostics.record(String::from("a"));
+
+        assert_eq!(diagnostics.report(), ["a (2 times)", "b"]);
+    }
+}
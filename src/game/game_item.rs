@@ -1,20 +1,151 @@
-use crate::{app::command::CommandHandler, clock::ticker::TickHandler, view::render::Renderable};
+use super::{
+    actors::{
+        asteroid::Asteroid, boss::Boss, bullet::Bullet, enemy::Enemy, explosion::Explosion,
+        high_score_table::HighScoreTable, level_banner::LevelBanner, missile::Missile,
+        power_up::PowerUp, ship::Ship,
+    },
+    hitbox::Hitbox,
+    radar::Contact,
+    ui::{
+        boss_health_bar::BossHealthBar, buttons::panel::ButtonPanel,
+        game_over_alert::GameOverAlert, health_bar::HealthBar, level::LevelIndicator,
+        missiles_bar::MissilesBar, score::Score,
+    },
+};
+use crate::{
+    app::command::{CommandHandler, CommandKind},
+    clock::ticker::TickHandler,
+    view::render::Renderable,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-pub trait GameItem: CommandHandler + Renderable + TickHandler {
+pub trait GameItem: CommandHandler + GameItemClone + Renderable + TickHandler {
     fn deleted(&self) -> bool {
         false
     }
 
+    // The remaining health of items that can be destroyed by collisions, such as the ship.
+    fn health(&self) -> Option<u8> {
+        None
+    }
+
+    // Invoked on every tick, before `handle_tick()`, with the positions of all actors.
+    fn handle_radar(&mut self, _contacts: &[Contact]) {}
+
+    // The regions that can collide with other actors, which must be within `viewport()`.
+    fn hitboxes(&self) -> Vec<Hitbox> {
+        vec![Hitbox::new(self.viewport())]
+    }
+
     fn kind(&self) -> GameItemKind {
         GameItemKind::Unspecified
     }
+
+    // Whether the item's state is restored when the World is rewound.
+    fn rewindable(&self) -> bool {
+        true
+    }
+
+    // The kinds of broadcast commands that `handle_command()` is invoked with. Commands that are
+    // addressed to the item, such as `Collide`, are delivered regardless.
+    fn subscriptions(&self) -> &'static [CommandKind] {
+        &[]
+    }
+
+    // A tagged copy of the item, so that a game in progress can be saved.
+    fn saved(&self) -> SavedGameItem;
+}
+
+// Allow `Box<dyn GameItem>` to be cloned, which is required to take snapshots of the World.
+pub trait GameItemClone {
+    fn clone_game_item(&self) -> Box<dyn GameItem>;
+}
+
+impl<T> GameItemClone for T
+where
+    T: 'static + GameItem + Clone,
+{
+    fn clone_game_item(&self) -> Box<dyn GameItem> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn GameItem> {
+    fn clone(&self) -> Self {
+        self.clone_game_item()
+    }
+}
+
+// Items are saved with a `type` field, which determines what they are loaded as.
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum SavedGameItem {
+    // Actors
+    Asteroid(Asteroid),
+    Boss(Boss),
+    Bullet(Bullet),
+    Enemy(Enemy),
+    Explosion(Explosion),
+    HighScoreTable(HighScoreTable),
+    LevelBanner(LevelBanner),
+    Missile(Missile),
+    PowerUp(PowerUp),
+    Ship(Ship),
+
+    // UI
+    BossHealthBar(BossHealthBar),
+    ButtonPanel(ButtonPanel),
+    GameOverAlert(GameOverAlert),
+    HealthBar(HealthBar),
+    LevelIndicator(LevelIndicator),
+    MissilesBar(MissilesBar),
+    Score(Score),
+}
+
+impl From<SavedGameItem> for Box<dyn GameItem> {
+    fn from(saved: SavedGameItem) -> Self {
+        match saved {
+            SavedGameItem::Asteroid(item) => Box::new(item),
+            SavedGameItem::Boss(item) => Box::new(item),
+            SavedGameItem::Bullet(item) => Box::new(item),
+            SavedGameItem::Enemy(item) => Box::new(item),
+            SavedGameItem::Explosion(item) => Box::new(item),
+            SavedGameItem::HighScoreTable(item) => Box::new(item),
+            SavedGameItem::LevelBanner(item) => Box::new(item),
+            SavedGameItem::Missile(item) => Box::new(item),
+            SavedGameItem::PowerUp(item) => Box::new(item),
+            SavedGameItem::Ship(item) => Box::new(item),
+            SavedGameItem::BossHealthBar(item) => Box::new(item),
+            SavedGameItem::ButtonPanel(item) => Box::new(item),
+            SavedGameItem::GameOverAlert(item) => Box::new(item),
+            SavedGameItem::HealthBar(item) => Box::new(item),
+            SavedGameItem::LevelIndicator(item) => Box::new(item),
+            SavedGameItem::MissilesBar(item) => Box::new(item),
+            SavedGameItem::Score(item) => Box::new(item),
+        }
+    }
+}
+
+impl Serialize for Box<dyn GameItem> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.saved().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Box<dyn GameItem> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SavedGameItem::deserialize(deserializer).map(Self::from)
+    }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum GameItemKind {
     Asteroid,
     Bullet,
+    Enemy,
+    EnemyBullet,
     Missile,
+    PowerUp,
     Ship,
     ShipWithShields,
 
@@ -22,7 +153,59 @@ pub enum GameItemKind {
 }
 
 impl GameItemKind {
+    // The collision-layer matrix: actors are only compared when their kinds can collide.
+    pub fn collides_with(self, other: Self) -> bool {
+        self.collides_one_way(other) || other.collides_one_way(self)
+    }
+
+    pub fn collides_with_anything(self) -> bool {
+        !matches!(self, GameItemKind::Unspecified)
+    }
+
+    pub fn damages_ship(self) -> bool {
+        matches!(
+            self,
+            GameItemKind::Asteroid | GameItemKind::Enemy | GameItemKind::EnemyBullet
+        )
+    }
+
+    pub fn is_ship(self) -> bool {
+        matches!(self, GameItemKind::Ship | GameItemKind::ShipWithShields)
+    }
+
     pub fn is_shootable(self) -> bool {
-        matches!(self, GameItemKind::Asteroid)
+        matches!(self, GameItemKind::Asteroid | GameItemKind::Enemy)
+    }
+
+    fn collides_one_way(self, other: Self) -> bool {
+        match self {
+            GameItemKind::Bullet | GameItemKind::Missile => other.is_shootable(),
+            GameItemKind::Ship | GameItemKind::ShipWithShields => {
+                other.damages_ship() || matches!(other, GameItemKind::PowerUp)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collides_with_is_symmetric() {
+        assert!(GameItemKind::Asteroid.collides_with(GameItemKind::Bullet));
+        assert!(GameItemKind::Bullet.collides_with(GameItemKind::Asteroid));
+        assert!(GameItemKind::PowerUp.collides_with(GameItemKind::ShipWithShields));
+    }
+
+    #[test]
+    fn actors_that_ignore_each_other_do_not_collide() {
+        assert!(!GameItemKind::Asteroid.collides_with(GameItemKind::Asteroid));
+        assert!(!GameItemKind::Asteroid.collides_with(GameItemKind::Enemy));
+        assert!(!GameItemKind::Bullet.collides_with(GameItemKind::Ship));
+        assert!(!GameItemKind::EnemyBullet.collides_with(GameItemKind::Enemy));
+        assert!(!GameItemKind::PowerUp.collides_with(GameItemKind::Bullet));
+        assert!(!GameItemKind::Unspecified.collides_with(GameItemKind::Ship));
     }
 }
@@ -0,0 +1,272 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    env, fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const INITIALS_LENGTH: usize = 3;
+const MAX_HIGH_SCORES: usize = 5;
+static FILE_NAME: &str = "high_scores.toml";
+static DIRECTORY_NAME: &str = "space_time_rewind";
+
+pub type Initials = [char; INITIALS_LENGTH];
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct HighScore {
+    pub date: String, // YYYY-MM-DD
+    pub initials: String,
+    pub level: u8,
+    pub score: u32,
+    pub seed: u64,
+}
+
+impl HighScore {
+    // A row of the high score table, where `rank` starts at 0.
+    pub fn row(&self, rank: usize, initials: &str) -> String {
+        format!(
+            "{:>2}. {initials:<3} {:>7}  Level {:<3} {}  Seed {}",
+            rank + 1,
+            self.score,
+            self.level,
+            self.date,
+            self.seed,
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct HighScores {
+    #[serde(skip)]
+    path: Option<PathBuf>, // High scores aren't persisted if this is `None`
+    #[serde(default)]
+    scores: Vec<HighScore>, // In descending order of score
+}
+
+impl HighScores {
+    // A missing or corrupt file results in an empty table, rather than an error, because losing the
+    // high scores is preferable to being unable to play. A file that can't be loaded is recorded in
+    // `errors`, and moved aside, so that it isn't replaced by the next high score. If it can't be
+    // moved, the high scores aren't saved.
+    pub fn load(path: Option<PathBuf>, errors: &mut Vec<String>) -> Self {
+        let mut path = path;
+        let mut high_scores = match path.as_deref().filter(|path| path.exists()).map(read) {
+            Some(Ok(high_scores)) => high_scores,
+            Some(Err(error)) => {
+                errors.push(format!("{error:#}"));
+                path = path.filter(|path| back_up(path, errors));
+                HighScores::default()
+            }
+            None => HighScores::default(),
+        };
+        high_scores.path = path;
+        high_scores
+            .scores
+            .sort_by_key(|high_score| Reverse(high_score.score));
+        high_scores.scores.truncate(MAX_HIGH_SCORES);
+        high_scores
+    }
+
+    // Insert the score, and return its rank (starting at 0), or `None` if it doesn't qualify.
+    pub fn insert(&mut self, high_score: HighScore) -> Option<usize> {
+        let rank = self.rank(high_score.score)?;
+        self.scores.insert(rank, high_score);
+        self.scores.truncate(MAX_HIGH_SCORES);
+        Some(rank)
+    }
+
+    // The rank that a score would have in the table, or `None` if it doesn't qualify.
+    pub fn rank(&self, score: u32) -> Option<usize> {
+        if score == 0 {
+            return None;
+        }
+        let rank = self
+            .scores
+            .iter()
+            .position(|high_score| score > high_score.score)
+            .unwrap_or(self.scores.len());
+        (rank < MAX_HIGH_SCORES).then_some(rank)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)
+                .with_context(|| format!("cannot create directory: {}", directory.display()))?;
+        }
+        let text = toml::to_string(self).context("cannot serialize high scores")?;
+        // Write to a temporary file first, so that the file isn't corrupted if writing fails.
+        let temporary_path = path.with_extension("toml.tmp");
+        fs::write(&temporary_path, text)
+            .with_context(|| format!("cannot write file: {}", temporary_path.display()))?;
+        fs::rename(&temporary_path, path)
+            .with_context(|| format!("cannot write file: {}", path.display()))
+    }
+
+    pub fn scores(&self) -> &[HighScore] {
+        &self.scores
+    }
+}
+
+// Move a file that can't be loaded to `high_scores.toml.bak`, and return whether it was moved.
+fn back_up(path: &Path, errors: &mut Vec<String>) -> bool {
+    let backup_path = path.with_extension("toml.bak");
+    match fs::rename(path, &backup_path) {
+        Ok(()) => {
+            errors.push(format!(
+                "the high scores file was moved to: {}",
+                backup_path.display()
+            ));
+            true
+        }
+        Err(error) => {
+            errors.push(format!(
+                "cannot move the high scores file to: {}, so high scores won't be saved: {error}",
+                backup_path.display()
+            ));
+            false
+        }
+    }
+}
+
+fn read(path: &Path) -> Result<HighScores> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("cannot read high scores file: {}", path.display()))?;
+    toml::from_str(&text).with_context(|| format!("invalid high scores file: {}", path.display()))
+}
+
+// `$XDG_DATA_HOME/space_time_rewind/high_scores.toml`, which defaults to `~/.local/share/...`.
+pub fn default_path() -> Option<PathBuf> {
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))?;
+    Some(data_home.join(DIRECTORY_NAME).join(FILE_NAME))
+}
+
+pub fn today() -> String {
+    let elapsed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let days = i64::try_from(elapsed.as_secs() / 86_400).unwrap();
+    let (year, month, day) = civil_from_days(days);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+// Convert days since 1970-01-01 to a (year, month, day), from:
+// https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = u32::try_from(day_of_year - (153 * mp + 2) / 5 + 1).unwrap();
+    let month = u32::try_from(if mp < 10 { mp + 3 } else { mp - 9 }).unwrap();
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn high_score(score: u32) -> HighScore {
+        HighScore {
+            date: String::from("2024-01-01"),
+            initials: String::from("ABC"),
+            level: 1,
+            score,
+            seed: 0,
+        }
+    }
+
+    fn temporary_path(name: &str) -> PathBuf {
+        env::temp_dir()
+            .join(format!("{DIRECTORY_NAME}_test_{}", std::process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn insert_keeps_scores_in_descending_order_and_truncates() {
+        let mut high_scores = HighScores::default();
+        for score in [10, 30, 20, 50, 40, 60] {
+            high_scores.insert(high_score(score));
+        }
+
+        let scores: Vec<u32> = high_scores.scores().iter().map(|s| s.score).collect();
+        assert_eq!(scores, vec![60, 50, 40, 30, 20]);
+        assert_eq!(high_scores.rank(20), None);
+        assert_eq!(high_scores.rank(45), Some(2));
+    }
+
+    #[test]
+    fn zero_does_not_qualify() {
+        assert_eq!(HighScores::default().rank(0), None);
+    }
+
+    #[test]
+    fn load_falls_back_to_an_empty_table_when_the_file_is_missing() {
+        let mut errors = Vec::new();
+        let high_scores = HighScores::load(Some(temporary_path("missing.toml")), &mut errors);
+
+        assert!(high_scores.scores().is_empty());
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn load_falls_back_to_an_empty_table_and_backs_up_the_file_when_it_is_corrupt() {
+        let path = temporary_path("corrupt.toml");
+        let backup_path = temporary_path("corrupt.toml.bak");
+        let text = "[[scores]]\nscore = \"not a number\"";
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, text).unwrap();
+
+        let mut errors = Vec::new();
+        let high_scores = HighScores::load(Some(path.clone()), &mut errors);
+        let backup = fs::read_to_string(&backup_path).unwrap();
+        fs::remove_file(&backup_path).unwrap();
+
+        assert!(high_scores.scores().is_empty());
+        assert_eq!(high_scores.path, Some(path.clone()));
+        assert!(!path.exists());
+        assert_eq!(backup, text);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with(&format!("invalid high scores file: {}: ", path.display())));
+        assert_eq!(
+            errors[1],
+            format!(
+                "the high scores file was moved to: {}",
+                backup_path.display()
+            )
+        );
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let path = temporary_path("round_trip.toml");
+        let mut errors = Vec::new();
+        let mut high_scores = HighScores::load(Some(path.clone()), &mut errors);
+        high_scores.insert(high_score(42));
+        high_scores.save().unwrap();
+
+        let loaded = HighScores::load(Some(path.clone()), &mut errors);
+
+        assert_eq!(loaded.scores(), &[high_score(42)]);
+        assert!(errors.is_empty());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn civil_from_days_converts_to_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_723), (2024, 1, 1));
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+    }
+}
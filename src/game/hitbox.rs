@@ -0,0 +1,142 @@
+use crate::view::{coordinates::Coordinates, viewport::Viewport};
+
+// A region of an actor that can collide with other actors. If a sprite is given, then only its
+// non-whitespace characters can collide, so that e.g. the corners of round sprites don't cause hits.
+#[derive(Clone, Copy, Debug)]
+pub struct Hitbox {
+    pub viewport: Viewport,
+    mask: Option<&'static str>,
+    rounded: bool, // Whether the corners can't collide, for sprites that are drawn square
+}
+
+impl Hitbox {
+    pub fn new(viewport: Viewport) -> Self {
+        Self {
+            viewport,
+            mask: None,
+            rounded: false,
+        }
+    }
+
+    pub fn new_with_mask(viewport: Viewport, text: &'static str) -> Self {
+        Self {
+            viewport,
+            mask: Some(text),
+            rounded: false,
+        }
+    }
+
+    // Exclude the four corner cells, even if the sprite draws them.
+    pub fn with_rounded_corners(mut self) -> Self {
+        self.rounded = true;
+        self
+    }
+
+    pub fn intersects(&self, other: Self) -> bool {
+        // Compare bounding boxes first, because comparing masks is much slower.
+        if !self.viewport.intersects(other.viewport) {
+            return false;
+        }
+        let other_cells = other.cells();
+        self.cells().iter().any(|cell| other_cells.contains(cell))
+    }
+
+    // The world coordinates of the cells that can collide.
+    fn cells(&self) -> Vec<Coordinates> {
+        let mut cells = Vec::new();
+        let height = self.viewport.height();
+        let width = self.viewport.width();
+        for row in 0..height {
+            for column in 0..width {
+                if self.solid(row, column) {
+                    let mut coordinates = self.viewport.bottom_left();
+                    // Rows of text are top to bottom, whereas y increases upwards.
+                    coordinates.offset_x(i16::from(column));
+                    coordinates.offset_y(i16::from(height - row - 1));
+                    cells.push(coordinates);
+                }
+            }
+        }
+        cells
+    }
+
+    fn solid(&self, row: u8, column: u8) -> bool {
+        let corner = (row == 0 || row == self.viewport.height() - 1)
+            && (column == 0 || column == self.viewport.width() - 1);
+        if self.rounded && corner {
+            return false;
+        }
+        let Some(text) = self.mask else {
+            return true;
+        };
+        text.lines()
+            .nth(usize::from(row))
+            .and_then(|line| line.chars().nth(usize::from(column)))
+            .is_some_and(|char| !char.is_whitespace())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEXT_DIAGONAL: &str = "\
+\x20█
+█\x20";
+
+    fn diagonal(x: u8, y: i8) -> Hitbox {
+        Hitbox::new_with_mask(
+            Viewport::new_with_coordinates(2, 2, Coordinates::new(x, y)),
+            TEXT_DIAGONAL,
+        )
+    }
+
+    fn solid(x: u8, y: i8) -> Hitbox {
+        Hitbox::new(Viewport::new_with_coordinates(1, 1, Coordinates::new(x, y)))
+    }
+
+    #[test]
+    fn intersects_only_non_whitespace_cells() {
+        let hitbox = diagonal(10, 10);
+
+        assert!(hitbox.intersects(solid(10, 10)));
+        assert!(hitbox.intersects(solid(11, 11)));
+        assert!(!hitbox.intersects(solid(11, 10)));
+        assert!(!hitbox.intersects(solid(10, 11)));
+    }
+
+    #[test]
+    fn intersects_when_wrapped_at_x_199() {
+        let hitbox = diagonal(199, 10);
+
+        assert!(hitbox.intersects(solid(199, 10)));
+        assert!(hitbox.intersects(solid(0, 11)));
+        assert!(!hitbox.intersects(solid(0, 10)));
+        assert!(!hitbox.intersects(solid(199, 11)));
+    }
+
+    #[test]
+    fn rounded_corners_do_not_intersect() {
+        let hitbox = Hitbox::new(Viewport::new_with_coordinates(
+            3,
+            3,
+            Coordinates::new(10, 10),
+        ))
+        .with_rounded_corners();
+
+        assert!(!hitbox.intersects(solid(10, 10)));
+        assert!(!hitbox.intersects(solid(12, 12)));
+        assert!(hitbox.intersects(solid(11, 10)));
+        assert!(hitbox.intersects(solid(10, 11)));
+    }
+
+    #[test]
+    fn masks_intersect_across_the_wrap() {
+        let left = diagonal(199, 10);
+        let right = diagonal(0, 10);
+
+        // The left mask's top-right cell is at (0, 11), which is the right mask's top-left blank.
+        assert!(!left.intersects(right));
+        assert!(left.intersects(diagonal(0, 11)));
+    }
+}
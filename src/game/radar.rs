@@ -0,0 +1,85 @@
+use super::{event_bus::Item, game_item::GameItemKind};
+use crate::view::viewport::Viewport;
+
+// The kind and position of an actor, which is provided to all actors on every tick, so that they
+// can react to each other's positions.
+#[derive(Clone, Copy)]
+pub struct Contact {
+    pub kind: GameItemKind,
+    pub viewport: Viewport,
+}
+
+pub fn scan(actors: &[Item]) -> Vec<Contact> {
+    actors
+        .iter()
+        .filter(|actor| !actor.deleted())
+        .map(|actor| Contact {
+            kind: actor.kind(),
+            viewport: actor.viewport(),
+        })
+        .collect()
+}
+
+pub fn find_ship(contacts: &[Contact]) -> Option<Contact> {
+    contacts
+        .iter()
+        .find(|contact| contact.kind.is_ship())
+        .copied()
+}
+
+// Find the nearest shootable contact whose center is above the center of the given viewport,
+// taking x-wrapping into account.
+pub fn find_nearest_target_above(contacts: &[Contact], viewport: Viewport) -> Option<Contact> {
+    let (_, y) = viewport.centered().as_tuple();
+    contacts
+        .iter()
+        .filter(|contact| contact.kind.is_shootable())
+        .filter_map(|contact| {
+            let (_, target_y) = contact.viewport.centered().as_tuple();
+            let dy = i16::from(target_y) - i16::from(y);
+            let dx = viewport.distance_x(contact.viewport);
+            (dy > 0).then_some((dx.abs() + dy, contact))
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, contact)| *contact)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::coordinates::Coordinates;
+
+    fn contact(kind: GameItemKind, x: u8, y: i8) -> Contact {
+        Contact {
+            kind,
+            viewport: Viewport::new_with_coordinates(1, 1, Coordinates::new(x, y)),
+        }
+    }
+
+    #[test]
+    fn find_nearest_target_above_ignores_targets_below_and_unshootable_contacts() {
+        let missile = Viewport::new_with_coordinates(1, 1, Coordinates::new(50, 10));
+        let contacts = [
+            contact(GameItemKind::Asteroid, 50, 5),
+            contact(GameItemKind::Bullet, 50, 11),
+            contact(GameItemKind::Enemy, 60, 20),
+        ];
+
+        let target = find_nearest_target_above(&contacts, missile).unwrap();
+
+        assert!(matches!(target.kind, GameItemKind::Enemy));
+    }
+
+    #[test]
+    fn find_nearest_target_above_handles_x_wrapping() {
+        let missile = Viewport::new_with_coordinates(1, 1, Coordinates::new(198, 10));
+        let contacts = [
+            contact(GameItemKind::Asteroid, 190, 12),
+            contact(GameItemKind::Asteroid, 2, 12),
+        ];
+
+        let target = find_nearest_target_above(&contacts, missile).unwrap();
+
+        assert_eq!(target.viewport.bottom_left().as_tuple(), (2, 12));
+    }
+}
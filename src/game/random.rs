@@ -0,0 +1,103 @@
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// A small seedable pseudo-random number generator (SplitMix64).
+// It is implemented here rather than depending on an external crate, so that a given seed
+// will always produce the same game, regardless of dependency upgrades.
+#[derive(Clone)]
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn below(&mut self, max: u64) -> u64 {
+        assert!(max > 0);
+        self.next_u64() % max
+    }
+
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+// The state is saved as a hexadecimal string, because TOML integers are signed.
+impl Serialize for Random {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{:016x}", self.state))
+    }
+}
+
+impl<'de> Deserialize<'de> for Random {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        u64::from_str_radix(&text, 16)
+            .map(Self::new)
+            .map_err(|_| D::Error::custom(format!("invalid random state: {text:?}")))
+    }
+}
+
+pub fn seed_from_time() -> u64 {
+    let elapsed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    // Keep seeds short enough to be easily read from the screen and typed.
+    u64::try_from(elapsed.as_nanos() % 1_000_000_000).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_produces_same_sequence() {
+        let mut a = Random::new(42);
+        let mut b = Random::new(42);
+
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn different_seeds_produce_different_sequences() {
+        let mut a = Random::new(1);
+        let mut b = Random::new(2);
+
+        assert_ne!(a.next_u64(), b.next_u64());
+    }
+
+    #[test]
+    fn below_is_always_less_than_max() {
+        let mut random = Random::new(7);
+
+        assert!((0..1000).all(|_| random.below(3) < 3));
+    }
+
+    #[test]
+    fn chance_of_0_is_never_true() {
+        let mut random = Random::new(7);
+
+        assert!((0..1000).all(|_| !random.chance(0, 2)));
+    }
+
+    #[test]
+    fn clone_continues_the_same_sequence() {
+        let mut random = Random::new(7);
+        random.next_u64();
+        let mut cloned = random.clone();
+
+        assert_eq!(random.next_u64(), cloned.next_u64());
+    }
+}
@@ -0,0 +1,144 @@
+use super::{
+    event_bus::{Item, ItemId},
+    spawner::main::Spawner,
+};
+use crate::{app::main::TICKS_PER_SECOND, view::coordinates::Coordinates};
+use std::collections::VecDeque;
+
+// Rewind faster than real-time, but slowly enough that the rewind can be seen playing backwards.
+const SNAPSHOTS_PER_TICK: usize = 2;
+
+#[derive(Clone)]
+pub struct Snapshot {
+    pub actors: Vec<Item>,
+    pub offset: Coordinates,
+    pub spawner: Spawner,
+    pub ui: Vec<(ItemId, Option<Item>)>, // `None` for UI items that are not rewindable
+}
+
+pub struct Rewind {
+    capacity: usize,
+    max_seconds: u16,
+    rewinding: bool,
+    snapshots: VecDeque<Snapshot>,
+}
+
+impl Rewind {
+    pub fn new(max_seconds: u16) -> Self {
+        let capacity = usize::from(max_seconds) * usize::from(TICKS_PER_SECOND);
+        Self {
+            capacity,
+            max_seconds,
+            rewinding: false,
+            snapshots: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn max_seconds(&self) -> u16 {
+        self.max_seconds
+    }
+
+    pub fn record(&mut self, snapshot: Snapshot) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+
+    pub fn restart(&mut self) {
+        self.rewinding = false;
+        self.snapshots.clear();
+    }
+
+    pub fn rewinding(&self) -> bool {
+        self.rewinding
+    }
+
+    pub fn start(&mut self) {
+        self.rewinding = !self.snapshots.is_empty();
+    }
+
+    // Return the next-oldest snapshot, or `None` if there is nothing left to rewind.
+    pub fn step(&mut self) -> Option<Snapshot> {
+        let mut snapshot = None;
+        for _ in 0..SNAPSHOTS_PER_TICK {
+            match self.snapshots.pop_back() {
+                Some(previous) => snapshot = Some(previous),
+                None => break,
+            }
+        }
+        if self.snapshots.is_empty() {
+            self.rewinding = false;
+        }
+        snapshot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_not_rewinding_when_new() {
+        let rewind = Rewind::new(1);
+
+        assert!(!rewind.rewinding());
+    }
+
+    #[test]
+    fn start_does_nothing_when_no_snapshots_are_recorded() {
+        let mut rewind = Rewind::new(1);
+        rewind.start();
+
+        assert!(!rewind.rewinding());
+        assert!(rewind.step().is_none());
+    }
+
+    #[test]
+    fn record_discards_the_oldest_snapshot_when_full() {
+        let mut rewind = Rewind::new(1);
+        for x in 0..=TICKS_PER_SECOND {
+            rewind.record(new_snapshot(u8::try_from(x).unwrap()));
+        }
+
+        assert_eq!(rewind.snapshots.len(), usize::from(TICKS_PER_SECOND));
+        assert_eq!(rewind.snapshots[0].offset.as_tuple(), (1, 0));
+    }
+
+    #[test]
+    fn step_returns_snapshots_from_newest_to_oldest() {
+        let mut rewind = Rewind::new(1);
+        for x in 0..4 {
+            rewind.record(new_snapshot(x));
+        }
+        rewind.start();
+
+        assert_eq!(rewind.step().unwrap().offset.as_tuple(), (2, 0));
+        assert!(rewind.rewinding());
+        assert_eq!(rewind.step().unwrap().offset.as_tuple(), (0, 0));
+        assert!(!rewind.rewinding());
+    }
+
+    #[test]
+    fn restart_stops_rewinding_and_clears_snapshots() {
+        let mut rewind = Rewind::new(1);
+        rewind.record(new_snapshot(0));
+        rewind.start();
+        rewind.restart();
+
+        assert!(!rewind.rewinding());
+        assert!(rewind.step().is_none());
+    }
+
+    fn new_snapshot(x: u8) -> Snapshot {
+        Snapshot {
+            actors: Vec::new(),
+            offset: Coordinates::new(x, 0),
+            spawner: Spawner::default(),
+            ui: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use crate::view::{factory::WORLD_WIDTH, viewport::Viewport};
+use std::collections::HashMap;
+
+// Cells are square-ish in terms of the largest sprites, and evenly divide the WORLD_WIDTH, so that
+// cells don't straddle the x-wrapping boundary.
+const CELL_WIDTH: u8 = 20;
+const CELL_HEIGHT: i8 = 8;
+
+// A broad phase for collision detection, which groups actors by the grid cells that they occupy,
+// so that only actors which share a cell need to be compared.
+#[derive(Default)]
+pub struct SpatialHash {
+    cells: HashMap<(u8, i8), Vec<usize>>,
+}
+
+impl SpatialHash {
+    pub fn insert(&mut self, index: usize, viewport: Viewport) {
+        let (x, y) = viewport.bottom_left().as_tuple();
+        let columns = cell_range(i16::from(x), i16::from(viewport.width()), CELL_WIDTH.into());
+        let rows = cell_range(
+            i16::from(y),
+            i16::from(viewport.height()),
+            CELL_HEIGHT.into(),
+        );
+        for column in columns {
+            // Account for x-wrapping.
+            let column =
+                u8::try_from(column.rem_euclid(i16::from(WORLD_WIDTH / CELL_WIDTH))).unwrap();
+            for row in rows.clone() {
+                let row = i8::try_from(row).unwrap();
+                self.cells.entry((column, row)).or_default().push(index);
+            }
+        }
+    }
+
+    // The pairs of indices that share at least one cell, in ascending order, so that collisions are
+    // always handled in the same order.
+    pub fn candidate_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for indices in self.cells.values() {
+            for (i, left) in indices.iter().enumerate() {
+                for right in &indices[i + 1..] {
+                    pairs.push((*left.min(right), *left.max(right)));
+                }
+            }
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+}
+
+fn cell_range(position: i16, length: i16, cell_length: i16) -> std::ops::RangeInclusive<i16> {
+    position.div_euclid(cell_length)..=(position + length - 1).div_euclid(cell_length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::coordinates::Coordinates;
+
+    fn viewport(x: u8, y: i8, width: u8) -> Viewport {
+        Viewport::new_with_coordinates(width, 1, Coordinates::new(x, y))
+    }
+
+    #[test]
+    fn pairs_only_actors_in_the_same_cell() {
+        let mut hash = SpatialHash::default();
+        hash.insert(0, viewport(0, 0, 1));
+        hash.insert(1, viewport(5, 0, 1));
+        hash.insert(2, viewport(50, 0, 1));
+        hash.insert(3, viewport(5, 20, 1));
+
+        assert_eq!(hash.candidate_pairs(), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn pairs_actors_that_span_multiple_cells_once() {
+        let mut hash = SpatialHash::default();
+        hash.insert(0, viewport(15, 0, 10));
+        hash.insert(1, viewport(18, 0, 10));
+
+        assert_eq!(hash.candidate_pairs(), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn pairs_actors_across_the_x_wrap() {
+        let mut hash = SpatialHash::default();
+        hash.insert(0, viewport(198, 0, 5));
+        hash.insert(1, viewport(1, 0, 1));
+
+        assert_eq!(hash.candidate_pairs(), vec![(0, 1)]);
+    }
+}
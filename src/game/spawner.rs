@@ -1,2 +1,2 @@
-mod levels;
+pub mod levels;
 pub mod main;
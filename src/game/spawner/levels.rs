@@ -1,45 +1,423 @@
 use crate::{
-    clock::ticker::{Frequency, Ticker},
     game::{
-        actors::{asteroid::Asteroid, power_up::PowerUp, ship::Ship},
+        actors::{asteroid::Asteroid, enemy::Enemy, power_up::PowerUp},
         game_item::GameItem,
+        random::Random,
+    },
+    view::{
+        coordinates::Coordinates,
+        factory::{WORLD_HEIGHT, WORLD_WIDTH},
+        viewport::Viewport,
     },
-    view::{coordinates::Coordinates, viewport::Viewport},
 };
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+const MAX_Y_SPREAD: u64 = 6; // When spawning at a random row
 
-pub fn initial(viewport: Viewport) -> Vec<Box<dyn GameItem>> {
-    let (_, y) = viewport.top_right().as_tuple();
+// The built-in levels are compiled into the binary, but can be replaced by passing `--levels <DIR>`.
+static BUILTIN_LEVELS: [(&str, &str); 3] = [
+    ("01.toml", include_str!("../../../levels/01.toml")),
+    ("02.toml", include_str!("../../../levels/02.toml")),
+    ("03.toml", include_str!("../../../levels/03.toml")),
+];
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "lowercase")]
+pub enum Goal {
+    Kills(u16),
+    Score(u32),
+    Seconds(u16),
+}
 
-    let mut actors: Vec<Box<dyn GameItem>> = vec![
-        Box::new(Ship::new(Coordinates::default())), // The ship will center itself when first rendered.
-        Box::new(Asteroid::new_small(Coordinates::new(1, y - 6))),
-        Box::new(Asteroid::new_large(Coordinates::new(15, y))),
-    ];
-    for x in (0..viewport.width()).step_by(25) {
-        actors.push(Box::new(Asteroid::new_medium(Coordinates::new(x, y))));
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LevelDefinition {
+    #[serde(default = "default_boss")]
+    pub boss: bool, // Whether a boss has to be defeated after the goal is reached
+    pub goal: Goal,
+    waves: Vec<Wave>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Wave {
+    every: Option<u16>, // Repeat the wave every N ticks
+    spawns: Vec<Spawn>,
+    tick: u16, // Ticks since the start of the level
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Spawn {
+    actor: ActorType,
+    #[serde(default = "default_chance")]
+    chance: u64, // Percent
+    #[serde(default = "default_count")]
+    count: u8,
+    size: Option<AsteroidSize>,
+    #[serde(default)]
+    x: Column,
+    #[serde(default)]
+    y: Row, // Rows below the top of the world
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum ActorType {
+    Asteroid,
+    Fighter,
+    Health,
+    Missile,
+    Strafer,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum AsteroidSize {
+    Large,
+    Medium,
+    Random,
+    Small,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Keyword {
+    #[default]
+    Random,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(untagged)]
+enum Column {
+    Fixed(u8),
+    Pattern { every: u8 }, // Repeat across the whole width of the world
+    Keyword(Keyword),
+}
+
+impl Default for Column {
+    fn default() -> Self {
+        Self::Keyword(Keyword::Random)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(untagged)]
+enum Row {
+    Fixed(u8),
+    Keyword(Keyword),
+}
+
+impl Default for Row {
+    fn default() -> Self {
+        Self::Keyword(Keyword::Random)
     }
-    actors
 }
 
-pub fn level1(ticker: &Ticker, viewport: Viewport) -> Vec<Box<dyn GameItem>> {
-    let mut actors: Vec<Box<dyn GameItem>> = Vec::new();
+impl LevelDefinition {
+    pub fn spawn(
+        &self,
+        difficulty: u8,
+        ticks: u16, // Since the start of the level
+        viewport: Viewport,
+        random: &mut Random,
+    ) -> Vec<Box<dyn GameItem>> {
+        self.waves
+            .iter()
+            .filter(|wave| wave.at(ticks))
+            .flat_map(|wave| wave.spawns.iter())
+            .flat_map(|spawn| spawn.actors(difficulty, viewport, random))
+            .collect()
+    }
 
-    if ticker.at(Frequency::Eight) {
+    fn validate(&self) -> Result<()> {
+        if self.waves.is_empty() {
+            return Err(anyhow!("a level must have at least one wave"));
+        }
+        for (i, wave) in self.waves.iter().enumerate() {
+            wave.validate()
+                .with_context(|| format!("invalid wave #{}", i + 1))?;
+        }
+        Ok(())
+    }
+}
+
+impl Wave {
+    #[allow(clippy::manual_is_multiple_of)]
+    fn at(&self, ticks: u16) -> bool {
+        match (ticks.checked_sub(self.tick), self.every) {
+            (Some(0), _) => true,
+            (Some(elapsed), Some(every)) => elapsed % every == 0,
+            _ => false,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.every == Some(0) {
+            return Err(anyhow!("`every` must be greater than 0"));
+        }
+        for (i, spawn) in self.spawns.iter().enumerate() {
+            spawn
+                .validate()
+                .with_context(|| format!("invalid spawn #{}", i + 1))?;
+        }
+        Ok(())
+    }
+}
+
+impl Spawn {
+    fn actors(
+        &self,
+        difficulty: u8,
+        viewport: Viewport,
+        random: &mut Random,
+    ) -> Vec<Box<dyn GameItem>> {
+        let mut count = self.count;
+        if let (ActorType::Asteroid, Column::Keyword(Keyword::Random)) = (self.actor, self.x) {
+            // The levels loop with an additional asteroid per randomly placed group each time.
+            count = count.saturating_add(difficulty);
+        }
+        let mut actors = Vec::new();
+        for _ in 0..count {
+            if !random.chance(self.chance, 100) {
+                continue;
+            }
+            for x in self.columns(viewport, random) {
+                let coordinates = Coordinates::new(x, self.row(viewport, random));
+                actors.push(self.actor(coordinates, random));
+            }
+        }
+        actors
+    }
+
+    fn actor(&self, coordinates: Coordinates, random: &mut Random) -> Box<dyn GameItem> {
+        match self.actor {
+            ActorType::Asteroid => new_asteroid(self.size, coordinates, random),
+            ActorType::Fighter => Box::new(Enemy::new_fighter(coordinates)),
+            ActorType::Health => Box::new(PowerUp::new_health(coordinates)),
+            ActorType::Missile => Box::new(PowerUp::new_missile(coordinates)),
+            ActorType::Strafer => Box::new(Enemy::new_strafer(coordinates)),
+        }
+    }
+
+    fn columns(&self, viewport: Viewport, random: &mut Random) -> Vec<u8> {
+        match self.x {
+            Column::Fixed(x) => vec![x],
+            Column::Pattern { every } => (0..viewport.width()).step_by(every.into()).collect(),
+            Column::Keyword(Keyword::Random) => {
+                vec![u8::try_from(random.below(u64::from(viewport.width()))).unwrap()]
+            }
+        }
+    }
+
+    fn row(&self, viewport: Viewport, random: &mut Random) -> i8 {
         let (_, y) = viewport.top_right().as_tuple();
+        let below_top = match self.y {
+            Row::Fixed(below_top) => u64::from(below_top),
+            Row::Keyword(Keyword::Random) => random.below(MAX_Y_SPREAD),
+        };
+        y - i8::try_from(below_top).unwrap()
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.actor != ActorType::Asteroid && self.size.is_some() {
+            return Err(anyhow!("`size` is only valid for asteroids"));
+        }
+        if self.chance > 100 {
+            return Err(anyhow!("`chance` must be a percentage from 0 to 100"));
+        }
+        if self.count == 0 {
+            return Err(anyhow!("`count` must be greater than 0"));
+        }
+        match self.x {
+            Column::Fixed(x) if x >= WORLD_WIDTH => {
+                return Err(anyhow!("`x` must be less than {WORLD_WIDTH}"));
+            }
+            Column::Pattern { every: 0 } => {
+                return Err(anyhow!("`x.every` must be greater than 0"));
+            }
+            _ => (),
+        }
+        if let Row::Fixed(y) = self.y {
+            if y >= WORLD_HEIGHT {
+                return Err(anyhow!("`y` must be less than {WORLD_HEIGHT}"));
+            }
+        }
+        Ok(())
+    }
+}
 
-        actors.push(Box::new(Asteroid::new_small(Coordinates::new(0, y))));
-        actors.push(Box::new(Asteroid::new_large(Coordinates::new(0, y - 4))));
-        actors.push(Box::new(PowerUp::new_health(Coordinates::new(
-            (38) - 3,
-            y - 1,
-        ))));
-        actors.push(Box::new(PowerUp::new_missile(Coordinates::new(
-            (37) - 3,
-            y - 5,
-        ))));
-        for x in (0..viewport.width()).step_by(25) {
-            actors.push(Box::new(Asteroid::new_medium(Coordinates::new(x, y))));
-        }
-    }
-    actors
+pub fn builtin_levels() -> Vec<LevelDefinition> {
+    BUILTIN_LEVELS
+        .iter()
+        .map(|(name, text)| parse(name, text).expect("Built-in levels are valid"))
+        .collect()
+}
+
+// Load the `*.toml` files in the given directory, in order of their file names.
+pub fn load_levels(directory: &Path) -> Result<Vec<LevelDefinition>> {
+    let entries = fs::read_dir(directory)
+        .with_context(|| format!("cannot read levels directory: {}", directory.display()))?;
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "toml")
+        {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    if paths.is_empty() {
+        return Err(anyhow!(
+            "no level files (*.toml) found in: {}",
+            directory.display()
+        ));
+    }
+    paths
+        .iter()
+        .map(|path| {
+            let text = fs::read_to_string(path)
+                .with_context(|| format!("cannot read level file: {}", path.display()))?;
+            parse(&path.display().to_string(), &text)
+        })
+        .collect()
+}
+
+fn parse(name: &str, text: &str) -> Result<LevelDefinition> {
+    let level: LevelDefinition =
+        toml::from_str(text).with_context(|| format!("invalid level file: {name}"))?;
+    level
+        .validate()
+        .with_context(|| format!("invalid level file: {name}"))?;
+    Ok(level)
+}
+
+fn new_asteroid(
+    size: Option<AsteroidSize>,
+    coordinates: Coordinates,
+    random: &mut Random,
+) -> Box<dyn GameItem> {
+    match size.unwrap_or(AsteroidSize::Random) {
+        AsteroidSize::Large => Box::new(Asteroid::new_large(coordinates)),
+        AsteroidSize::Medium => Box::new(Asteroid::new_medium(coordinates)),
+        AsteroidSize::Small => Box::new(Asteroid::new_small(coordinates)),
+        AsteroidSize::Random => {
+            let size = match random.below(3) {
+                0 => AsteroidSize::Small,
+                1 => AsteroidSize::Medium,
+                _ => AsteroidSize::Large,
+            };
+            new_asteroid(Some(size), coordinates, random)
+        }
+    }
+}
+
+fn default_boss() -> bool {
+    true
+}
+
+fn default_chance() -> u64 {
+    100
+}
+
+fn default_count() -> u8 {
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_levels_are_valid() {
+        assert_eq!(builtin_levels().len(), BUILTIN_LEVELS.len());
+    }
+
+    #[test]
+    fn parse_fails_when_there_are_no_waves() {
+        let error = parse("test", "goal = { kills = 1 }\nwaves = []").unwrap_err();
+
+        assert!(format!("{error:#}").contains("at least one wave"));
+    }
+
+    #[test]
+    fn parse_fails_when_a_field_is_unknown() {
+        let text = r#"
+            goal = { kills = 1 }
+            [[waves]]
+            tick = 0
+            spawns = [{ actor = "asteroid", colour = "red" }]
+        "#;
+
+        assert!(parse("test", text).is_err());
+    }
+
+    #[test]
+    fn parse_fails_when_x_is_outside_of_the_world() {
+        let text = r#"
+            goal = { kills = 1 }
+            [[waves]]
+            tick = 0
+            spawns = [{ actor = "asteroid", x = 200 }]
+        "#;
+        let error = parse("test", text).unwrap_err();
+
+        assert!(format!("{error:#}").contains("wave #1: invalid spawn #1: `x` must be less"));
+    }
+
+    #[test]
+    fn parse_fails_when_size_is_given_for_a_power_up() {
+        let text = r#"
+            goal = { kills = 1 }
+            [[waves]]
+            tick = 0
+            spawns = [{ actor = "health", size = "large" }]
+        "#;
+
+        assert!(parse("test", text).is_err());
+    }
+
+    #[test]
+    fn spawn_places_actors_across_the_world_when_x_is_a_pattern() {
+        let text = r#"
+            goal = { kills = 1 }
+            [[waves]]
+            tick = 0
+            spawns = [{ actor = "asteroid", x = { every = 50 }, y = 0 }]
+        "#;
+        let level = parse("test", text).unwrap();
+        let actors = level.spawn(0, 0, Viewport::new_for_world(), &mut Random::new(0));
+
+        assert_eq!(actors.len(), 4);
+    }
+
+    #[test]
+    fn wave_repeats_every_n_ticks_after_the_first_tick() {
+        let wave = Wave {
+            every: Some(10),
+            spawns: Vec::new(),
+            tick: 5,
+        };
+
+        assert!(!wave.at(0));
+        assert!(wave.at(5));
+        assert!(!wave.at(10));
+        assert!(wave.at(15));
+    }
+
+    #[test]
+    fn wave_without_every_happens_once() {
+        let wave = Wave {
+            every: None,
+            spawns: Vec::new(),
+            tick: 5,
+        };
+
+        assert!(wave.at(5));
+        assert!(!wave.at(15));
+    }
 }
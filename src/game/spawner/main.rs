@@ -1,70 +1,317 @@
-use super::levels::{initial, level1};
+use super::levels::{builtin_levels, Goal, LevelDefinition};
 use crate::{
-    clock::ticker::Ticker,
+    app::{command::Command, keymap::Keymap, main::TICKS_PER_SECOND},
+    clock::countdown::Countdown,
     game::{
+        actors::{boss::Boss, level_banner::LevelBanner, ship::Ship},
         game_item::GameItem,
+        random::Random,
         ui::{
-            buttons::panel::ButtonPanel, health_bar::HealthBar, missiles_bar::MissilesBar,
-            score::Score,
+            boss_health_bar::BossHealthBar, buttons::panel::ButtonPanel, health_bar::HealthBar,
+            level::LevelIndicator, missiles_bar::MissilesBar, score::Score,
         },
     },
-    view::viewport::Viewport,
+    view::{coordinates::Coordinates, viewport::Viewport},
 };
+use serde::{Deserialize, Serialize};
+use std::rc::Rc;
 
-enum Level {
+const BOSS_BASE_HP: u8 = 30;
+const BOSS_HP_PER_DIFFICULTY: u8 = 10;
+const TRANSITION_COUNT: u16 = TICKS_PER_SECOND * 3; // 3 seconds
+
+#[derive(Clone, Deserialize, Serialize)]
+enum Stage {
+    Boss { defeated: bool }, // No waves are spawned while the boss is alive
     Initial,
-    Level1,
+    Playing,
+    Transition(Countdown), // A brief lull between levels
 }
 
-impl Level {
-    // TODO Remove the following #[allow] once the TODO in the method body is addressed.
-    #[allow(clippy::match_single_binding)]
-    fn next(&mut self, _: &Ticker) {
-        *self = match self {
-            // TODO Advance levels. Consider using `Countdown` or `Ticker` to time the advancement.
-            _ => Self::Level1,
-        }
-    }
+#[derive(Clone, Default, Deserialize, Serialize)]
+struct Progress {
+    kills: u16,
+    score: u32,
+    ticks: u16,
+}
 
-    fn spawn(&self, ticker: &Ticker, viewport: Viewport) -> Vec<Box<dyn GameItem>> {
-        match self {
-            Self::Initial => initial(viewport),
-            Self::Level1 => level1(ticker, viewport),
+impl Progress {
+    fn reached(&self, goal: Goal) -> bool {
+        match goal {
+            Goal::Kills(kills) => self.kills >= kills,
+            Goal::Score(score) => self.score >= score,
+            Goal::Seconds(seconds) => self.ticks >= seconds.saturating_mul(TICKS_PER_SECOND),
         }
     }
 }
 
+#[derive(Clone, Deserialize, Serialize)]
 pub struct Spawner {
-    level: Level,
+    #[serde(skip)]
+    commands: Vec<Command>, // Drained on every tick, so there are none to save
+    level: u8, // Starts at 1
+    #[serde(skip)]
+    levels: Rc<[LevelDefinition]>, // Shared between snapshots, because levels are immutable
+    progress: Progress,
+    random: Random,
+    score: u32, // Across all levels
+    seed: u64,
+    stage: Stage,
 }
 
 impl Default for Spawner {
     fn default() -> Self {
-        Spawner {
-            level: Level::Initial,
-        }
+        Self::new(0, builtin_levels().into())
     }
 }
 
 impl Spawner {
-    pub fn actors(&mut self, ticker: &Ticker, viewport: Viewport) -> Vec<Box<dyn GameItem>> {
-        let actors = self.level.spawn(ticker, viewport);
-        self.level.next(ticker);
-        actors
+    pub fn new(seed: u64, levels: Rc<[LevelDefinition]>) -> Self {
+        assert!(!levels.is_empty());
+        Spawner {
+            commands: Vec::new(),
+            level: 1,
+            levels,
+            progress: Progress::default(),
+            random: Random::new(seed),
+            score: 0,
+            seed,
+            stage: Stage::Initial,
+        }
+    }
+
+    pub fn actors(&mut self, viewport: Viewport) -> Vec<Box<dyn GameItem>> {
+        match &mut self.stage {
+            Stage::Boss { defeated } => {
+                if *defeated {
+                    self.next_level();
+                    return vec![Box::new(LevelBanner::new(self.level))];
+                }
+                Vec::new()
+            }
+            Stage::Initial => {
+                self.stage = Stage::Playing;
+                self.commands.push(Command::UpdateLevel(self.level));
+                vec![
+                    Box::new(Ship::new(Coordinates::default())), // The ship will center itself when first rendered.
+                    Box::new(LevelBanner::new(self.level)),
+                ]
+            }
+            Stage::Playing => {
+                if self.progress.reached(self.definition().goal) {
+                    if self.definition().boss {
+                        self.stage = Stage::Boss { defeated: false };
+                        return vec![Box::new(self.boss(viewport))];
+                    }
+                    self.next_level();
+                    return vec![Box::new(LevelBanner::new(self.level))];
+                }
+                let difficulty = self.difficulty();
+                let actors = self.levels[self.index()].spawn(
+                    difficulty,
+                    self.progress.ticks,
+                    viewport,
+                    &mut self.random,
+                );
+                self.progress.ticks = self.progress.ticks.saturating_add(1);
+                actors
+            }
+            Stage::Transition(countdown) => {
+                countdown.down();
+                if countdown.off() {
+                    self.stage = Stage::Playing;
+                }
+                Vec::new()
+            }
+        }
+    }
+
+    pub fn boss_defeated(&mut self) {
+        if let Stage::Boss { defeated } = &mut self.stage {
+            *defeated = true;
+        }
+    }
+
+    // Commands that are emitted by the spawner, such as when advancing to the next level.
+    pub fn commands(&mut self) -> Vec<Command> {
+        self.commands.drain(..).collect()
+    }
+
+    pub fn increase_score(&mut self, points: u32) {
+        self.score = self.score.saturating_add(points);
+        if let Stage::Playing = self.stage {
+            self.progress.kills = self.progress.kills.saturating_add(1);
+            self.progress.score = self.progress.score.saturating_add(points);
+        }
     }
 
-    pub fn ui(&self) -> Vec<Box<dyn GameItem>> {
+    pub fn ui(&self, keymap: &Keymap) -> Vec<Box<dyn GameItem>> {
         vec![
+            Box::new(BossHealthBar::default()),
             Box::new(HealthBar::default()),
             Box::new(MissilesBar::default()),
+            Box::new(LevelIndicator::default()),
             Box::new(Score::default()),
-            // Render the health, missiles, and score UIs before the button panel, so that they'll
-            // be rendered below the panel when the viewport is very narrow.
-            Box::new(ButtonPanel::default()),
+            // Render the health, missiles, level, and score UIs before the button panel, so that
+            // they'll be rendered below the panel when the viewport is very narrow.
+            Box::new(ButtonPanel::new(keymap)),
         ]
     }
 
-    pub fn restart(&mut self) {
-        self.level = Level::Initial;
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    // Continue from a saved Spawner, with the current levels, because levels aren't saved.
+    pub fn restore(&mut self, saved: Spawner) {
+        *self = Self {
+            levels: self.levels.clone(),
+            ..saved
+        };
+    }
+
+    pub fn restart(&mut self, seed: u64) {
+        *self = Self::new(seed, self.levels.clone());
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    // The boss enters from the top of the world, above the ship.
+    fn boss(&self, viewport: Viewport) -> Boss {
+        let (_, y) = viewport.top_right().as_tuple();
+        let hp =
+            BOSS_BASE_HP.saturating_add(BOSS_HP_PER_DIFFICULTY.saturating_mul(self.difficulty()));
+        Boss::new(Coordinates::new(0, y), hp)
+    }
+
+    fn definition(&self) -> &LevelDefinition {
+        &self.levels[self.index()]
+    }
+
+    // The levels loop with increased difficulty after the last level is completed.
+    fn difficulty(&self) -> u8 {
+        u8::try_from(usize::from(self.level - 1) / self.levels.len()).unwrap_or(u8::MAX)
+    }
+
+    fn index(&self) -> usize {
+        usize::from(self.level - 1) % self.levels.len()
+    }
+
+    fn next_level(&mut self) {
+        self.level = self.level.saturating_add(1);
+        self.progress = Progress::default();
+        let mut countdown = Countdown::new(TRANSITION_COUNT);
+        countdown.restart();
+        self.stage = Stage::Transition(countdown);
+        self.commands.push(Command::UpdateLevel(self.level));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_at_level_1() {
+        let mut spawner = Spawner::default();
+        spawn(&mut spawner, 1);
+
+        assert_eq!(spawner.level, 1);
+        assert!(matches!(spawner.commands()[..], [Command::UpdateLevel(1)]));
+    }
+
+    #[test]
+    fn advances_to_level_2_when_the_goal_is_reached_and_the_boss_is_defeated() {
+        let mut spawner = Spawner::default();
+        let Goal::Seconds(seconds) = spawner.levels[0].goal else {
+            panic!("Level 1 has a duration goal");
+        };
+        spawn(&mut spawner, 2 + usize::from(seconds * TICKS_PER_SECOND));
+
+        assert_eq!(spawner.level, 1);
+        assert!(matches!(spawner.stage, Stage::Boss { defeated: false }));
+
+        spawner.boss_defeated();
+        spawn(&mut spawner, 1);
+
+        assert_eq!(spawner.level, 2);
+        assert!(matches!(spawner.stage, Stage::Transition(_)));
+        assert!(matches!(
+            spawner.commands()[..],
+            [Command::UpdateLevel(1), Command::UpdateLevel(2)]
+        ));
+    }
+
+    #[test]
+    fn spawns_only_the_boss_after_the_goal_is_reached() {
+        let mut spawner = Spawner {
+            progress: Progress {
+                kills: u16::MAX,
+                score: u32::MAX,
+                ticks: u16::MAX,
+            },
+            stage: Stage::Playing,
+            ..Spawner::default()
+        };
+
+        assert_eq!(spawner.actors(Viewport::new_for_world()).len(), 1);
+        assert!(spawner.actors(Viewport::new_for_world()).is_empty());
+    }
+
+    #[test]
+    fn spawns_nothing_during_the_transition() {
+        let mut spawner = Spawner::default();
+        spawner.next_level();
+
+        let actors = spawner.actors(Viewport::new_for_world());
+
+        assert!(actors.is_empty());
+    }
+
+    #[test]
+    fn kills_and_score_count_towards_the_goal() {
+        let mut spawner = Spawner {
+            stage: Stage::Playing,
+            ..Spawner::default()
+        };
+        spawner.increase_score(5);
+        spawner.increase_score(5);
+
+        assert!(spawner.progress.reached(Goal::Kills(2)));
+        assert!(spawner.progress.reached(Goal::Score(10)));
+        assert!(!spawner.progress.reached(Goal::Score(11)));
+    }
+
+    #[test]
+    fn loops_with_increased_difficulty_after_the_last_level() {
+        let mut spawner = Spawner::default();
+        for _ in 0..spawner.levels.len() {
+            spawner.next_level();
+        }
+
+        assert_eq!(spawner.index(), 0);
+        assert_eq!(spawner.difficulty(), 1);
+    }
+
+    #[test]
+    fn restart_returns_to_level_1() {
+        let mut spawner = Spawner::default();
+        spawner.next_level();
+        spawner.restart(0);
+
+        assert_eq!(spawner.level, 1);
+        assert!(matches!(spawner.stage, Stage::Initial));
+    }
+
+    fn spawn(spawner: &mut Spawner, n: usize) {
+        for _ in 0..n {
+            spawner.actors(Viewport::new_for_world());
+        }
     }
 }
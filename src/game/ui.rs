@@ -1,5 +1,7 @@
+pub mod boss_health_bar;
 pub mod buttons;
 pub mod game_over_alert;
 pub mod health_bar;
+pub mod level;
 pub mod missiles_bar;
 pub mod score;
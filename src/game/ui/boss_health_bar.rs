@@ -0,0 +1,130 @@
+use crate::{
+    app::{
+        color::ColorTheme,
+        command::{Command, CommandHandler, CommandKind, NO_COMMANDS},
+    },
+    clock::ticker::TickHandler,
+    game::game_item::{GameItem, SavedGameItem},
+    view::{
+        coordinates::Coordinates, render::Renderable, renderer::Renderer, util::chars_width,
+        viewport::Viewport,
+    },
+};
+use serde::{Deserialize, Serialize};
+use tui::{style::Style, text::Span};
+
+const HEIGHT: u8 = 1;
+const SEGMENTS: u8 = 10; // Boss health is scaled, because bosses have much more health than the ship
+static TEXT_HEADER: &str = "Boss ";
+static TEXT_CURRENT: &str = "▮";
+static TEXT_LOST: &str = "▯";
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct BossHealthBar {
+    coordinates: Coordinates,
+    current: u8,
+    max: u8,
+}
+
+impl CommandHandler for BossHealthBar {
+    fn handle_command(&mut self, command: Command) -> Vec<Command> {
+        match command {
+            Command::UpdateBossHealth(current, max) => {
+                self.current = current;
+                self.max = max;
+            }
+            // Make room for the game over alert's seed.
+            Command::GameOver => self.current = 0,
+            _ => (),
+        }
+        NO_COMMANDS
+    }
+}
+
+impl Default for BossHealthBar {
+    fn default() -> Self {
+        Self {
+            coordinates: Coordinates::new(5, 0), // Align the header with the health bar's
+            current: 0,
+            max: 0,
+        }
+    }
+}
+
+impl GameItem for BossHealthBar {
+    fn saved(&self) -> SavedGameItem {
+        SavedGameItem::BossHealthBar(self.clone())
+    }
+
+    fn subscriptions(&self) -> &'static [CommandKind] {
+        &[CommandKind::GameOver, CommandKind::UpdateBossHealth]
+    }
+}
+
+impl Renderable for BossHealthBar {
+    fn render(&self, renderer: &mut Renderer) {
+        if self.hidden() {
+            return;
+        }
+        let header = span(TEXT_HEADER.to_string(), ColorTheme::BossHealthHeader);
+        let current = span(
+            TEXT_CURRENT.repeat(usize::from(self.segments())),
+            ColorTheme::BossHealthCurrent,
+        );
+        let lost = span(
+            TEXT_LOST.repeat(usize::from(SEGMENTS - self.segments())),
+            ColorTheme::BossHealthLost,
+        );
+        renderer.render_spans(self.viewport(), vec![header, current, lost]);
+    }
+
+    fn viewport(&self) -> Viewport {
+        let width = chars_width(TEXT_HEADER) + SEGMENTS;
+        Viewport::new_with_coordinates(width, HEIGHT, self.coordinates)
+    }
+}
+
+impl TickHandler for BossHealthBar {}
+
+impl BossHealthBar {
+    // The bar is only shown while a boss is alive.
+    fn hidden(&self) -> bool {
+        self.current == 0 || self.max == 0
+    }
+
+    // Round up, so that a boss with any health left has at least one segment.
+    fn segments(&self) -> u8 {
+        let segments =
+            (u16::from(self.current) * u16::from(SEGMENTS)).div_ceil(u16::from(self.max));
+        u8::try_from(segments).unwrap_or(SEGMENTS).min(SEGMENTS)
+    }
+}
+
+fn span<'a>(text: String, color: ColorTheme) -> Span<'a> {
+    Span::styled(text, Style::from(color))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segments_are_scaled_and_rounded_up() {
+        let mut bar = BossHealthBar::default();
+        bar.handle_command(Command::UpdateBossHealth(1, 40));
+        assert_eq!(bar.segments(), 1);
+
+        bar.handle_command(Command::UpdateBossHealth(40, 40));
+        assert_eq!(bar.segments(), SEGMENTS);
+    }
+
+    #[test]
+    fn is_hidden_after_the_boss_is_defeated() {
+        let mut bar = BossHealthBar::default();
+        bar.handle_command(Command::UpdateBossHealth(10, 40));
+        assert!(!bar.hidden());
+
+        bar.handle_command(Command::UpdateBossHealth(0, 40));
+        assert!(bar.hidden());
+    }
+}
@@ -1,4 +1,5 @@
 mod button;
 mod missile;
 pub mod panel;
+mod rewind;
 mod shields;
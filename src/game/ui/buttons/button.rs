@@ -8,52 +8,122 @@ use crate::{
         viewport::Viewport,
     },
 };
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use super::{missile::MissileButton, shields::ShieldsButton};
+use super::{missile::MissileButton, rewind::RewindButton, shields::ShieldsButton};
 
 const ACTIVE_COUNT: u16 = TICKS_PER_SECOND / 10; // 100ms
+const DISABLED_REWIND_COUNT: u16 = TICKS_PER_SECOND * 15; // 15 seconds
 const DISABLED_SHIELDS_COUNT: u16 = TICKS_PER_SECOND * 30; // 30 seconds
 
-#[derive(Copy, Clone)]
+#[derive(Clone, Copy, Deserialize, Serialize)]
 pub enum ButtonSize {
     Condensed,
     Full,
 }
 
-pub trait Button {
+pub trait Button: ButtonClone {
     fn color(&self, active: bool, disabled: bool) -> ColorTheme;
 
     fn handle_command(&mut self, _command: Command) -> Option<Command> {
         None
     }
 
-    fn text(&self, size: ButtonSize) -> &'static str;
+    fn name(&self) -> &'static str;
+
+    // A tagged copy of the button, so that it can be saved.
+    fn saved(&self) -> SavedButton;
+}
+
+pub trait ButtonClone {
+    fn clone_button(&self) -> Box<dyn Button>;
+}
+
+impl<T> ButtonClone for T
+where
+    T: 'static + Button + Clone,
+{
+    fn clone_button(&self) -> Box<dyn Button> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Button> {
+    fn clone(&self) -> Self {
+        self.clone_button()
+    }
+}
+
+// Buttons are saved with a `type` field, which determines what they are loaded as.
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum SavedButton {
+    Missile(MissileButton),
+    Rewind(RewindButton),
+    Shields(ShieldsButton),
+}
+
+impl From<SavedButton> for Box<dyn Button> {
+    fn from(saved: SavedButton) -> Self {
+        match saved {
+            SavedButton::Missile(button) => Box::new(button),
+            SavedButton::Rewind(button) => Box::new(button),
+            SavedButton::Shields(button) => Box::new(button),
+        }
+    }
 }
 
+impl Serialize for Box<dyn Button> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.saved().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Box<dyn Button> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SavedButton::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
 pub struct ButtonContainer {
     active: Countdown,
     button: Box<dyn Button>,
     disabled: Countdown,
+    key: String, // The label of the key that presses the button
 }
 
 impl ButtonContainer {
-    pub fn new_missiles() -> Self {
-        Self::new(Box::new(MissileButton::default()))
+    pub fn new_missiles(key: String) -> Self {
+        Self::new(Box::new(MissileButton::default()), key)
+    }
+
+    pub fn new_rewind(key: String) -> Self {
+        Self::new_disableable(
+            Box::new(RewindButton::default()),
+            key,
+            DISABLED_REWIND_COUNT,
+        )
     }
 
-    pub fn new_shields() -> Self {
-        Self::new_disableable(Box::new(ShieldsButton::default()), DISABLED_SHIELDS_COUNT)
+    pub fn new_shields(key: String) -> Self {
+        Self::new_disableable(
+            Box::new(ShieldsButton::default()),
+            key,
+            DISABLED_SHIELDS_COUNT,
+        )
     }
 
-    fn new(button: Box<dyn Button>) -> Self {
-        Self::new_disableable(button, 0)
+    fn new(button: Box<dyn Button>, key: String) -> Self {
+        Self::new_disableable(button, key, 0)
     }
 
-    fn new_disableable(button: Box<dyn Button>, disabled_count: u16) -> Self {
+    fn new_disableable(button: Box<dyn Button>, key: String, disabled_count: u16) -> Self {
         Self {
             active: Countdown::new(ACTIVE_COUNT),
             disabled: Countdown::new(disabled_count),
             button,
+            key,
         }
     }
 
@@ -75,20 +145,46 @@ impl ButtonContainer {
 
     pub fn render(&self, renderer: &mut Renderer, coordinates: Coordinates, size: ButtonSize) {
         let color = self.button.color(self.active.on(), self.disabled.on());
-        let text = self.button.text(size);
+        let text = self.text(size);
         let viewport = self.viewport(coordinates, size);
-        renderer.render(viewport, text, color);
+        renderer.render(viewport, &text, color);
     }
 
     pub fn height(&self, size: ButtonSize) -> u8 {
-        chars_height(self.button.text(size))
+        chars_height(&self.text(size))
     }
 
     pub fn width(&self, size: ButtonSize) -> u8 {
-        chars_width(self.button.text(size))
+        chars_width(&self.text(size))
+    }
+
+    // E.g. `│Missile [j]│`, or `│[j]│` when condensed, with a rounded border.
+    fn text(&self, size: ButtonSize) -> String {
+        let label = match size {
+            ButtonSize::Condensed => format!("[{}]", self.key),
+            ButtonSize::Full => format!("{} [{}]", self.button.name(), self.key),
+        };
+        let border = "─".repeat(label.chars().count());
+        format!("╭{border}╮\n│{label}│\n╰{border}╯")
     }
 
     fn viewport(&self, coordinates: Coordinates, size: ButtonSize) -> Viewport {
         Viewport::new_with_coordinates(self.width(size), self.height(size), coordinates)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_includes_the_key() {
+        let button = ButtonContainer::new_missiles(String::from("Ctrl+m"));
+
+        assert_eq!(
+            button.text(ButtonSize::Full),
+            "╭────────────────╮\n│Missile [Ctrl+m]│\n╰────────────────╯"
+        );
+        assert_eq!(button.width(ButtonSize::Condensed), 10);
+    }
+}
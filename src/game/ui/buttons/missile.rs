@@ -1,16 +1,8 @@
-use super::button::{Button, ButtonSize};
+use super::button::{Button, SavedButton};
 use crate::app::{color::ColorTheme, command::Command};
+use serde::{Deserialize, Serialize};
 
-static TEXT_CONDENSED: &str = "\
-╭───╮
-│[j]│
-╰───╯";
-static TEXT_FULL: &str = "\
-╭───────────╮
-│Missile [j]│
-╰───────────╯";
-
-#[derive(Default)]
+#[derive(Clone, Default, Deserialize, Serialize)]
 pub struct MissileButton {
     disabled: bool,
 }
@@ -40,10 +32,11 @@ impl Button for MissileButton {
         }
     }
 
-    fn text(&self, size: ButtonSize) -> &'static str {
-        match size {
-            ButtonSize::Condensed => TEXT_CONDENSED,
-            ButtonSize::Full => TEXT_FULL,
-        }
+    fn name(&self) -> &'static str {
+        "Missile"
+    }
+
+    fn saved(&self) -> SavedButton {
+        SavedButton::Missile(self.clone())
     }
 }
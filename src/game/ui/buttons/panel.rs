@@ -1,14 +1,19 @@
 use super::button::{ButtonContainer, ButtonSize};
 use crate::{
-    app::command::{Command, CommandHandler, NO_COMMANDS},
+    app::{
+        command::{Command, CommandHandler, CommandKind, NO_COMMANDS},
+        keymap::{Action, Keymap},
+    },
     clock::ticker::{TickHandler, Ticker},
-    game::game_item::GameItem,
+    game::game_item::{GameItem, SavedGameItem},
     view::{coordinates::Coordinates, render::Renderable, renderer::Renderer, viewport::Viewport},
 };
+use serde::{Deserialize, Serialize};
 
 const MARGIN_LENGTH: u8 = 1;
-const MIN_FULL_WIDTH: u8 = 58;
+const MIN_FULL_WIDTH: u8 = 76;
 
+#[derive(Clone, Deserialize, Serialize)]
 pub struct ButtonPanel {
     buttons: Vec<ButtonContainer>,
     coordinates: Coordinates,
@@ -35,20 +40,27 @@ impl CommandHandler for ButtonPanel {
     }
 }
 
-impl Default for ButtonPanel {
-    fn default() -> Self {
-        Self {
-            buttons: vec![
-                ButtonContainer::new_missiles(),
-                ButtonContainer::new_shields(),
-            ],
-            coordinates: Coordinates::default(), // Will be re-aligned during `render()`
-            size: ButtonSize::Full,
-        }
+impl GameItem for ButtonPanel {
+    fn rewindable(&self) -> bool {
+        // Otherwise rewinding would also restore the rewind button's charges and cooldown.
+        false
     }
-}
 
-impl GameItem for ButtonPanel {}
+    fn saved(&self) -> SavedGameItem {
+        SavedGameItem::ButtonPanel(self.clone())
+    }
+
+    fn subscriptions(&self) -> &'static [CommandKind] {
+        &[
+            CommandKind::GameOver,
+            CommandKind::PressMissileButton,
+            CommandKind::PressRewindButton,
+            CommandKind::PressShieldsButton,
+            CommandKind::UiViewportInitializedOrChanged,
+            CommandKind::UpdateMissiles,
+        ]
+    }
+}
 
 impl Renderable for ButtonPanel {
     // The lint warning is a false positive: https://github.com/rust-lang/rust-clippy/issues/7414
@@ -69,14 +81,28 @@ impl Renderable for ButtonPanel {
 }
 
 impl TickHandler for ButtonPanel {
-    fn handle_tick(&mut self, ticker: &Ticker, _: Viewport) {
+    fn handle_tick(&mut self, ticker: &Ticker, _: Viewport) -> Vec<Command> {
         for button in self.buttons.iter_mut() {
             button.handle_tick(ticker)
         }
+        NO_COMMANDS
     }
 }
 
 impl ButtonPanel {
+    // The buttons are labelled with the keys that press them.
+    pub fn new(keymap: &Keymap) -> Self {
+        Self {
+            buttons: vec![
+                ButtonContainer::new_missiles(keymap.label(Action::Missile)),
+                ButtonContainer::new_shields(keymap.label(Action::Shields)),
+                ButtonContainer::new_rewind(keymap.label(Action::Rewind)),
+            ],
+            coordinates: Coordinates::default(), // Will be re-aligned during `render()`
+            size: ButtonSize::Full,
+        }
+    }
+
     fn align(&mut self, viewport: Viewport) {
         self.size = if viewport.width() < MIN_FULL_WIDTH {
             ButtonSize::Condensed
@@ -0,0 +1,50 @@
+use super::button::{Button, SavedButton};
+use crate::app::{color::ColorTheme, command::Command};
+use serde::{Deserialize, Serialize};
+
+const INITIAL_CHARGES: u8 = 3;
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct RewindButton {
+    charges: u8,
+}
+
+impl Button for RewindButton {
+    fn color(&self, active: bool, disabled: bool) -> ColorTheme {
+        if active {
+            ColorTheme::RewindButtonActive
+        } else if disabled || self.charges == 0 {
+            ColorTheme::DisabledButton
+        } else {
+            ColorTheme::RewindButton
+        }
+    }
+
+    fn handle_command(&mut self, command: Command) -> Option<Command> {
+        if self.charges == 0 {
+            return None;
+        }
+        if let Command::PressRewindButton = command {
+            self.charges -= 1;
+            Some(Command::Rewind)
+        } else {
+            None
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Rewind"
+    }
+
+    fn saved(&self) -> SavedButton {
+        SavedButton::Rewind(self.clone())
+    }
+}
+
+impl Default for RewindButton {
+    fn default() -> Self {
+        Self {
+            charges: INITIAL_CHARGES,
+        }
+    }
+}
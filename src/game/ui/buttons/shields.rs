@@ -1,16 +1,8 @@
-use super::button::{Button, ButtonSize};
+use super::button::{Button, SavedButton};
 use crate::app::{color::ColorTheme, command::Command};
+use serde::{Deserialize, Serialize};
 
-static TEXT_CONDENSED: &str = "\
-╭───╮
-│[k]│
-╰───╯";
-static TEXT_FULL: &str = "\
-╭───────────╮
-│Shields [k]│
-╰───────────╯";
-
-#[derive(Default)]
+#[derive(Clone, Default, Deserialize, Serialize)]
 pub struct ShieldsButton {}
 
 impl Button for ShieldsButton {
@@ -32,10 +24,11 @@ impl Button for ShieldsButton {
         }
     }
 
-    fn text(&self, size: ButtonSize) -> &'static str {
-        match size {
-            ButtonSize::Condensed => TEXT_CONDENSED,
-            ButtonSize::Full => TEXT_FULL,
-        }
+    fn name(&self) -> &'static str {
+        "Shields"
+    }
+
+    fn saved(&self) -> SavedButton {
+        SavedButton::Shields(self.clone())
     }
 }
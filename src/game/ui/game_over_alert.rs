@@ -1,7 +1,7 @@
 use crate::{
     app::{color::ColorTheme, command::CommandHandler},
     clock::ticker::TickHandler,
-    game::game_item::GameItem,
+    game::game_item::{GameItem, SavedGameItem},
     view::{
         coordinates::Coordinates,
         render::Renderable,
@@ -10,24 +10,41 @@ use crate::{
         viewport::Viewport,
     },
 };
+use serde::{Deserialize, Serialize};
+use tui::{style::Style, text::Span};
 
+// Add whitespace to overwrite the health and missiles bars.
 static TEXT: &str = "\
 Game over!   \x20
-Press [r] to restart or [q] to quit "; // Add whitespace to the first line to overwrite the health bar
+\x20                                  \x20";
 
+#[derive(Clone, Deserialize, Serialize)]
 pub struct GameOverAlert {
     coordinates: Coordinates,
     height: u8,
+    seed: u64,
     width: u8,
 }
 
-impl GameItem for GameOverAlert {}
+impl GameItem for GameOverAlert {
+    fn saved(&self) -> SavedGameItem {
+        SavedGameItem::GameOverAlert(self.clone())
+    }
+}
 
 impl CommandHandler for GameOverAlert {}
 
 impl Renderable for GameOverAlert {
     fn render(&self, renderer: &mut Renderer) {
         renderer.render(self.viewport(), TEXT, ColorTheme::GameOver);
+
+        // Render the seed below the alert, so that the game can be replayed using `--seed`.
+        let text = self.text_seed();
+        let width = chars_width(text.as_str());
+        let (x, y) = self.coordinates.as_tuple();
+        let viewport = Viewport::new_with_coordinates(width, 1, Coordinates::new(x, y - 1));
+        let spans = vec![Span::styled(text, Style::from(ColorTheme::GameOverSeed))];
+        renderer.render_spans(viewport, spans);
     }
 
     fn viewport(&self) -> Viewport {
@@ -38,13 +55,18 @@ impl Renderable for GameOverAlert {
 impl TickHandler for GameOverAlert {}
 
 impl GameOverAlert {
-    pub fn new() -> Self {
+    pub fn new(seed: u64) -> Self {
         let height = chars_height(TEXT);
         let width = chars_width(TEXT);
         Self {
             coordinates: Coordinates::new(1, 1),
             height,
+            seed,
             width,
         }
     }
+
+    fn text_seed(&self) -> String {
+        format!("Seed {}", self.seed)
+    }
 }
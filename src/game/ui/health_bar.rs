@@ -1,25 +1,24 @@
 use crate::{
     app::{
         color::ColorTheme,
-        command::{Command, CommandHandler, NO_COMMANDS},
+        command::{Command, CommandHandler, CommandKind, NO_COMMANDS},
     },
     clock::ticker::TickHandler,
-    game::game_item::GameItem,
+    game::game_item::{GameItem, SavedGameItem},
     view::{
         coordinates::Coordinates, render::Renderable, renderer::Renderer, util::chars_width,
         viewport::Viewport,
     },
 };
-use tui::{
-    style::{Color, Style},
-    text::Span,
-};
+use serde::{Deserialize, Serialize};
+use tui::{style::Style, text::Span};
 
 const HEIGHT: u8 = 1;
 static TEXT_HEADER: &str = "Health ";
 static TEXT_CURRENT: &str = "▮";
 static TEXT_LOST: &str = "▯";
 
+#[derive(Clone, Deserialize, Serialize)]
 pub struct HealthBar {
     coordinates: Coordinates,
     current: u8,
@@ -43,7 +42,15 @@ impl Default for HealthBar {
     }
 }
 
-impl GameItem for HealthBar {}
+impl GameItem for HealthBar {
+    fn saved(&self) -> SavedGameItem {
+        SavedGameItem::HealthBar(self.clone())
+    }
+
+    fn subscriptions(&self) -> &'static [CommandKind] {
+        &[CommandKind::UpdateHealth]
+    }
+}
 
 impl Renderable for HealthBar {
     fn render(&self, renderer: &mut Renderer) {
@@ -83,5 +90,5 @@ impl HealthBar {
 }
 
 fn span<'a>(text: String, color: ColorTheme) -> Span<'a> {
-    Span::styled(text, Style::default().fg(Color::from(color)))
+    Span::styled(text, Style::from(color))
 }
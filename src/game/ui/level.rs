@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::max;
+
+use crate::{
+    app::{
+        color::ColorTheme,
+        command::{Command, CommandHandler, CommandKind, NO_COMMANDS},
+    },
+    clock::ticker::TickHandler,
+    game::game_item::{GameItem, SavedGameItem},
+    view::{
+        coordinates::Coordinates, render::Renderable, renderer::Renderer, util::chars_width,
+        viewport::Viewport,
+    },
+};
+use tui::{style::Style, text::Span};
+
+const MARGIN_LENGTH: u8 = 1;
+const HEIGHT: u8 = 2;
+const SCORE_WIDTH: u8 = 7; // Reserve space for the score, which is right-aligned to the left of this.
+static TEXT_HEADER: &str = "Level";
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct LevelIndicator {
+    coordinates: Coordinates,
+    level: u8,
+}
+
+impl CommandHandler for LevelIndicator {
+    fn handle_command(&mut self, command: Command) -> Vec<Command> {
+        match command {
+            Command::UpdateLevel(level) => {
+                self.level = level;
+            }
+            Command::UiViewportInitializedOrChanged(viewport) => {
+                self.align(viewport);
+            }
+            _ => (),
+        }
+        NO_COMMANDS
+    }
+}
+
+impl Default for LevelIndicator {
+    fn default() -> Self {
+        Self {
+            coordinates: Coordinates::default(), // Will be re-aligned during `render()`
+            level: 1,
+        }
+    }
+}
+
+impl GameItem for LevelIndicator {
+    fn saved(&self) -> SavedGameItem {
+        SavedGameItem::LevelIndicator(self.clone())
+    }
+
+    fn subscriptions(&self) -> &'static [CommandKind] {
+        &[
+            CommandKind::UiViewportInitializedOrChanged,
+            CommandKind::UpdateLevel,
+        ]
+    }
+}
+
+impl Renderable for LevelIndicator {
+    fn render(&self, renderer: &mut Renderer) {
+        let (x, y) = self.coordinates.as_tuple();
+
+        let width = self.width();
+        let header_offset = width - chars_width(TEXT_HEADER);
+        let header_spans = vec![Span::styled(
+            TEXT_HEADER,
+            Style::from(ColorTheme::LevelHeader),
+        )];
+        let header_coordinates = Coordinates::new(x + header_offset, y + 1);
+
+        let number_offset = width - chars_width(self.text().as_str());
+        let number_spans = vec![Span::styled(
+            self.text(),
+            Style::from(ColorTheme::LevelNumber),
+        )];
+        let number_coordinates = Coordinates::new(x + number_offset, y);
+
+        let viewport = self.viewport();
+        renderer.render_spans(viewport.with_coordinates(number_coordinates), number_spans);
+        renderer.render_spans(viewport.with_coordinates(header_coordinates), header_spans);
+    }
+
+    fn viewport(&self) -> Viewport {
+        Viewport::new_with_coordinates(self.width(), HEIGHT, self.coordinates)
+    }
+}
+
+impl TickHandler for LevelIndicator {}
+
+impl LevelIndicator {
+    fn align(&mut self, viewport: Viewport) {
+        let (x, _) = viewport.top_right().as_tuple();
+        let (_, y) = viewport.bottom_left().as_tuple();
+        let right_width = self.width() + SCORE_WIDTH + 2 * MARGIN_LENGTH;
+        let x = (x + 1).saturating_sub(right_width);
+        let y = y + i8::try_from(MARGIN_LENGTH).unwrap();
+        self.coordinates = Coordinates::new(x, y);
+    }
+
+    fn text(&self) -> String {
+        self.level.to_string()
+    }
+
+    fn width(&self) -> u8 {
+        max(chars_width(TEXT_HEADER), chars_width(self.text().as_str()))
+    }
+}
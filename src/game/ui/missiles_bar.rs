@@ -1,25 +1,24 @@
 use crate::{
     app::{
         color::ColorTheme,
-        command::{Command, CommandHandler, NO_COMMANDS},
+        command::{Command, CommandHandler, CommandKind, NO_COMMANDS},
     },
     clock::ticker::TickHandler,
-    game::game_item::GameItem,
+    game::game_item::{GameItem, SavedGameItem},
     view::{
         coordinates::Coordinates, render::Renderable, renderer::Renderer, util::chars_width,
         viewport::Viewport,
     },
 };
-use tui::{
-    style::{Color, Style},
-    text::Span,
-};
+use serde::{Deserialize, Serialize};
+use tui::{style::Style, text::Span};
 
 const HEIGHT: u8 = 1;
 static TEXT_HEADER: &str = "Missiles ";
 static TEXT_CURRENT: &str = "▮";
 static TEXT_USED: &str = "▯";
 
+#[derive(Clone, Deserialize, Serialize)]
 pub struct MissilesBar {
     coordinates: Coordinates,
     current: u8,
@@ -42,7 +41,15 @@ impl Default for MissilesBar {
     }
 }
 
-impl GameItem for MissilesBar {}
+impl GameItem for MissilesBar {
+    fn saved(&self) -> SavedGameItem {
+        SavedGameItem::MissilesBar(self.clone())
+    }
+
+    fn subscriptions(&self) -> &'static [CommandKind] {
+        &[CommandKind::UpdateMissiles]
+    }
+}
 
 impl Renderable for MissilesBar {
     fn render(&self, renderer: &mut Renderer) {
@@ -82,5 +89,5 @@ impl MissilesBar {
 }
 
 fn span<'a>(text: String, color: ColorTheme) -> Span<'a> {
-    Span::styled(text, Style::default().fg(Color::from(color)))
+    Span::styled(text, Style::from(color))
 }
@@ -1,26 +1,25 @@
+use serde::{Deserialize, Serialize};
 use std::cmp::max;
 
 use crate::{
     app::{
         color::ColorTheme,
-        command::{Command, CommandHandler, NO_COMMANDS},
+        command::{Command, CommandHandler, CommandKind, NO_COMMANDS},
     },
     clock::ticker::TickHandler,
-    game::game_item::GameItem,
+    game::game_item::{GameItem, SavedGameItem},
     view::{
         coordinates::Coordinates, render::Renderable, renderer::Renderer, util::chars_width,
         viewport::Viewport,
     },
 };
-use tui::{
-    style::{Color, Style},
-    text::Span,
-};
+use tui::{style::Style, text::Span};
 
 const MARGIN_LENGTH: u8 = 1;
 const HEIGHT: u8 = 2;
 static TEXT_HEADER: &str = "Score";
 
+#[derive(Clone, Deserialize, Serialize)]
 pub struct Score {
     coordinates: Coordinates,
     score: u32,
@@ -47,7 +46,18 @@ impl Default for Score {
     }
 }
 
-impl GameItem for Score {}
+impl GameItem for Score {
+    fn saved(&self) -> SavedGameItem {
+        SavedGameItem::Score(self.clone())
+    }
+
+    fn subscriptions(&self) -> &'static [CommandKind] {
+        &[
+            CommandKind::IncreaseScore,
+            CommandKind::UiViewportInitializedOrChanged,
+        ]
+    }
+}
 
 impl Renderable for Score {
     fn render(&self, renderer: &mut Renderer) {
@@ -57,14 +67,14 @@ impl Renderable for Score {
         let header_offset = width - chars_width(TEXT_HEADER);
         let header_spans = vec![Span::styled(
             TEXT_HEADER,
-            Style::default().fg(Color::from(ColorTheme::ScoreHeader)),
+            Style::from(ColorTheme::ScoreHeader),
         )];
         let header_coordinates = Coordinates::new(x + header_offset, y + 1);
 
         let points_offset = width - chars_width(self.text().as_str());
         let points_spans = vec![Span::styled(
             self.text(),
-            Style::default().fg(Color::from(ColorTheme::ScorePoints)),
+            Style::from(ColorTheme::ScorePoints),
         )];
         let points_coordinates = Coordinates::new(x + points_offset, y);
 
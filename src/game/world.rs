@@ -1,22 +1,53 @@
 use super::{
-    actors::{bullet::Bullet, explosion::Explosion, missile::Missile},
+    actors::{
+        asteroid::Asteroid, bullet::Bullet, explosion::Explosion, high_score_table::HighScoreTable,
+        missile::Missile,
+    },
+    event_bus::{Diagnostics, Event, EventBus, Item, ItemId, Target},
     game_item::GameItem,
-    spawner::main::Spawner,
+    high_scores::{today, HighScore, HighScores, Initials},
+    radar::scan,
+    random::seed_from_time,
+    rewind::{Rewind, Snapshot},
+    spatial_hash::SpatialHash,
+    spawner::{levels::LevelDefinition, main::Spawner},
     ui::game_over_alert::GameOverAlert,
 };
 use crate::{
-    app::command::{Command, NO_COMMANDS},
+    app::{command::Command, keymap::Keymap},
     clock::ticker::Ticker,
     view::{coordinates::Coordinates, viewport::Viewport},
 };
-use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::{mem, rc::Rc};
 
-const ACTORS_BROADCAST_CYCLES: u8 = 3;
+pub const DEFAULT_REWIND_SECONDS: u16 = 5;
+
+// A game in progress, which can be continued later. The rewind history isn't saved.
+#[derive(Deserialize, Serialize)]
+pub struct SavedWorld {
+    actors: Vec<Item>,
+    actors_viewport: Option<Viewport>,
+    next_id: ItemId,
+    offset: Coordinates,
+    spawner: Spawner,
+    ui: Vec<Item>,
+    ui_viewport: Option<Viewport>,
+}
 
 pub struct World {
-    pub actors: Vec<Box<dyn GameItem>>,
+    pub actors: Vec<Item>,
     pub offset: Coordinates,
-    pub ui: Vec<Box<dyn GameItem>>,
+    pub ui: Vec<Item>,
+    diagnostics: Diagnostics,
+    game_over: bool,
+    high_scores: HighScores,
+    keymap: Rc<Keymap>,                // For labelling the buttons
+    new_high_score: Option<HighScore>, // Awaiting the player's initials
+    next_id: ItemId,
+    rewind: Rewind,
+    save_errors: Vec<String>, // High scores that couldn't be saved
+    seed: Option<u64>, // A fixed seed is re-used when restarting, so that the game can be replayed.
     spawner: Spawner,
 
     actors_viewport: Option<Viewport>,
@@ -24,12 +55,35 @@ pub struct World {
     world_viewport: Viewport,
 }
 
-impl Default for World {
-    fn default() -> Self {
+// How the World responded to a broadcast command.
+#[derive(Eq, PartialEq)]
+enum Response {
+    Consumed, // Not delivered to any item
+    Observed, // Also delivered to the items that subscribe to it
+    Ignored,
+}
+
+impl World {
+    pub fn new(
+        seed: Option<u64>,
+        levels: Rc<[LevelDefinition]>,
+        high_scores: HighScores,
+        keymap: Rc<Keymap>,
+        rewind_seconds: u16,
+    ) -> Self {
         Self {
             actors: Vec::new(),
+            diagnostics: Diagnostics::default(),
+            game_over: false,
+            high_scores,
+            keymap,
+            new_high_score: None,
+            next_id: ItemId::default(),
             offset: Coordinates::default(),
-            spawner: Spawner::default(),
+            rewind: Rewind::new(rewind_seconds),
+            save_errors: Vec::new(),
+            seed,
+            spawner: Spawner::new(seed.unwrap_or_else(seed_from_time), levels),
             ui: Vec::new(),
 
             actors_viewport: None,
@@ -37,44 +91,63 @@ impl Default for World {
             world_viewport: Viewport::new_for_world(),
         }
     }
-}
 
-impl World {
-    pub fn broadcast_collisions(&mut self) -> Result<()> {
-        let commands = self.detect_collisions();
-        self.broadcast_commands(&commands)
+    // A World with a fixed seed, the built-in levels, the default keymap and rewind window, and high
+    // scores that aren't saved.
+    #[cfg(test)]
+    pub fn for_test(seed: u64) -> Self {
+        Self::new(
+            Some(seed),
+            super::spawner::levels::builtin_levels().into(),
+            HighScores::default(),
+            Rc::default(),
+            DEFAULT_REWIND_SECONDS,
+        )
     }
 
-    pub fn broadcast_commands(&mut self, commands: &[Command]) -> Result<()> {
-        let commands: Vec<Command> = commands
-            .iter()
-            .flat_map(|command| self.broadcast_command(*command))
-            .collect();
-        validate_unhandled_commands(&commands)
+    #[cfg(test)]
+    pub fn with_high_scores(self, high_scores: HighScores) -> Self {
+        Self {
+            high_scores,
+            ..self
+        }
+    }
+
+    pub fn add_actor(&mut self, actor: Box<dyn GameItem>) {
+        let actor = self.new_item(actor);
+        self.actors.push(actor);
+    }
+
+    pub fn broadcast_collisions(&mut self) {
+        if self.rewinding() {
+            // Snapshots are replayed as they were, so collisions must not be handled twice.
+            return;
+        }
+        self.detect_collisions();
     }
 
-    pub fn broadcast_actors_viewport(&mut self, viewport: Viewport) -> Result<()> {
+    pub fn broadcast_commands(&mut self, commands: &[Command]) {
+        for command in commands {
+            self.dispatch(Event::broadcast(*command));
+        }
+    }
+
+    pub fn broadcast_actors_viewport(&mut self, viewport: Viewport) {
         if let Some(previous_viewport) = self.actors_viewport {
             if previous_viewport != viewport {
-                self.broadcast_commands(&[Command::ActorsViewportChanged(viewport)])?;
+                self.broadcast_commands(&[Command::ActorsViewportChanged(viewport)]);
             }
         } else {
-            self.broadcast_commands(&[Command::ActorsViewportInitialized(viewport)])?;
+            self.broadcast_commands(&[Command::ActorsViewportInitialized(viewport)]);
         }
         self.actors_viewport = Some(viewport);
-        Ok(())
     }
 
-    pub fn broadcast_ui_viewport(&mut self, viewport: Viewport) -> Result<()> {
-        if let Some(previous_viewport) = self.ui_viewport {
-            if previous_viewport != viewport {
-                self.broadcast_command_to_ui(Command::UiViewportInitializedOrChanged(viewport))?;
-            }
-        } else {
-            self.broadcast_command_to_ui(Command::UiViewportInitializedOrChanged(viewport))?;
+    pub fn broadcast_ui_viewport(&mut self, viewport: Viewport) {
+        if self.ui_viewport != Some(viewport) {
+            self.broadcast_commands(&[Command::UiViewportInitializedOrChanged(viewport)]);
         }
         self.ui_viewport = Some(viewport);
-        Ok(())
     }
 
     pub fn handle_tick(&mut self, ticker: &Ticker) {
@@ -87,60 +160,265 @@ impl World {
             self.ui_viewport = None;
 
             // Actors are updated on every tick, but the UI is initialized only once on the first tick.
-            self.ui = self.spawner.ui();
+            let ui = self.spawner.ui(&self.keymap);
+            self.ui = ui.into_iter().map(|item| self.new_item(item)).collect();
+        }
+
+        if self.rewinding() {
+            self.rewind_step();
+            return;
+        }
+        self.rewind.record(self.snapshot());
+
+        let contacts = scan(&self.actors);
+        for actor in &mut self.actors {
+            actor.handle_radar(&contacts);
         }
 
         let world_viewport = self.world_viewport;
-        self.game_items_iter_mut()
-            .for_each(|handler| handler.handle_tick(ticker, world_viewport));
+        let mut events: Vec<Event> = self
+            .game_items_iter_mut()
+            .flat_map(|item| {
+                let id = item.id;
+                item.handle_tick(ticker, world_viewport)
+                    .into_iter()
+                    .map(move |command| Event::published_by(id, command))
+            })
+            .collect();
         self.actors.retain(|actor| !actor.deleted());
+        for actor in self.spawner.actors(world_viewport) {
+            self.add_actor(actor);
+        }
+        events.extend(self.spawner.commands().into_iter().map(Event::broadcast));
+        for event in events {
+            self.dispatch(event);
+        }
+    }
+
+    // Problems that were recorded while the game was played, such as unhandled commands.
+    pub fn diagnostics(&self) -> &Diagnostics {
+        &self.diagnostics
+    }
+
+    // Files that couldn't be saved, which the player needs to know about, unlike the diagnostics.
+    pub fn save_errors(&self) -> &[String] {
+        &self.save_errors
+    }
+
+    pub fn entering_initials(&self) -> bool {
+        self.new_high_score.is_some()
+    }
+
+    // Whether the ship has been destroyed since the game was (re)started.
+    pub fn game_over(&self) -> bool {
+        self.game_over
+    }
+
+    pub fn high_scores(&self) -> &HighScores {
+        &self.high_scores
+    }
+
+    pub fn level(&self) -> u8 {
+        self.spawner.level()
+    }
+
+    // The seed of the current game.
+    pub fn seed(&self) -> u64 {
+        self.spawner.seed()
+    }
+
+    pub fn score(&self) -> u32 {
+        self.spawner.score()
+    }
+
+    // 0 once the ship has been destroyed.
+    pub fn ship_health(&self) -> u8 {
         self.actors
-            .extend(self.spawner.actors(ticker, world_viewport));
+            .iter()
+            .filter(|actor| actor.kind().is_ship())
+            .find_map(|actor| actor.health())
+            .unwrap_or_default()
+    }
+
+    pub fn rewinding(&self) -> bool {
+        self.rewind.rewinding()
+    }
+
+    // How far back the game can be rewound.
+    pub fn rewind_seconds(&self) -> u16 {
+        self.rewind.max_seconds()
+    }
+
+    // Continue a saved game. The viewports are restored as well, so that the ship isn't re-centered
+    // when the World is next rendered.
+    pub fn restore(&mut self, saved: SavedWorld) {
+        self.actors = saved.actors;
+        self.actors_viewport = saved.actors_viewport;
+        self.game_over = false;
+        self.new_high_score = None;
+        self.next_id = saved.next_id;
+        self.offset = saved.offset;
+        self.rewind.restart();
+        self.spawner.restore(saved.spawner);
+        self.ui = saved.ui;
+        self.ui_viewport = saved.ui_viewport;
     }
 
-    fn broadcast_command(&mut self, command: Command) -> Vec<Command> {
-        let mut commands = vec![command];
-        for _ in 0..ACTORS_BROADCAST_CYCLES {
-            commands = commands
-                .into_iter()
-                .flat_map(|command| self.notify_handlers(command))
-                .collect();
+    pub fn save(&self) -> SavedWorld {
+        SavedWorld {
+            actors: self.actors.clone(),
+            actors_viewport: self.actors_viewport,
+            next_id: self.next_id,
+            offset: self.offset,
+            spawner: self.spawner.clone(),
+            ui: self.ui.clone(),
+            ui_viewport: self.ui_viewport,
         }
-        commands
     }
 
-    fn broadcast_command_to_ui(&mut self, command: Command) -> Result<()> {
-        let commands = self.notify_ui_handlers(command);
-        validate_unhandled_commands(&commands)
+    // Deliver an event, and then the events that it leads to, until there are none left.
+    fn dispatch(&mut self, event: Event) {
+        let mut bus = EventBus::default();
+        bus.publish(event);
+        while let Some((cause, event)) = bus.next() {
+            for caused in self.deliver(event) {
+                if let Err(error) = bus.publish_caused(cause, caused) {
+                    self.diagnostics.record(error.to_string());
+                }
+            }
+        }
+    }
+
+    // Returns the events that the recipients published in response.
+    fn deliver(&mut self, event: Event) -> Vec<Event> {
+        let command = event.command;
+        let id = match event.target {
+            Target::All => {
+                let response = self.handle_command(command);
+                if response == Response::Consumed {
+                    return Vec::new();
+                }
+                let kind = command.kind();
+                let mut subscribed = false;
+                let mut events = Vec::new();
+                for item in self.game_items_iter_mut() {
+                    if item.subscriptions().contains(&kind) {
+                        subscribed = true;
+                        let id = item.id;
+                        events.extend(
+                            item.handle_command(command)
+                                .into_iter()
+                                .map(|command| Event::published_by(id, command)),
+                        );
+                    }
+                }
+                if !subscribed && response == Response::Ignored {
+                    self.diagnostics.record(format!("unhandled event: {event}"));
+                }
+                return events;
+            }
+            Target::Item(id) => id,
+        };
+        let Some(item) = self.game_items_iter_mut().find(|item| item.id == id) else {
+            self.diagnostics.record(format!(
+                "unhandled event: {event}, whose target doesn't exist"
+            ));
+            return Vec::new();
+        };
+        item.handle_command(command)
+            .into_iter()
+            .map(|command| Event::published_by(id, command))
+            .collect()
     }
 
-    fn consumed_command(&mut self, command: Command) -> bool {
+    // Handle the World's own part of a broadcast command.
+    fn handle_command(&mut self, command: Command) -> Response {
         match command {
             Command::ActorsViewportChanged(viewport) => {
                 self.align_offset(viewport);
-                return false;
+                return Response::Observed;
+            }
+            // The World keeps track of the viewports itself, even before there are items to align.
+            Command::ActorsViewportInitialized(_) | Command::UiViewportInitializedOrChanged(_) => {
+                return Response::Observed;
+            }
+            Command::AddAsteroid(coordinates, size, drift) => {
+                self.add_actor(Box::new(Asteroid::new_fragment(coordinates, size, drift)))
+            }
+            Command::AddBullet(coordinates) => self.add_actor(Box::new(Bullet::new(coordinates))),
+            Command::AddEnemyBullet(coordinates, dx) => {
+                self.add_actor(Box::new(Bullet::new_enemy(coordinates, dx)))
             }
-            Command::AddBullet(coordinates) => self.actors.push(Box::new(Bullet::new(coordinates))),
             Command::AddExplosion(coordinates) => {
-                self.actors.push(Box::new(Explosion::new(coordinates)))
+                self.add_actor(Box::new(Explosion::new(coordinates)))
             }
-            Command::AddMissile(coordinates) => {
-                self.actors.push(Box::new(Missile::new(coordinates)))
+            Command::AddMissile(coordinates) => self.add_actor(Box::new(Missile::new(coordinates))),
+            Command::BossDefeated => {
+                self.spawner.boss_defeated();
+                return Response::Observed;
             }
             Command::GameOver => {
-                self.ui.push(Box::new(GameOverAlert::new()));
-                return false;
+                let alert = self.new_item(Box::new(GameOverAlert::new(self.spawner.seed())));
+                self.ui.push(alert);
+                self.game_over = true;
+                self.prompt_for_initials();
+                return Response::Observed;
+            }
+            Command::IncreaseScore(points) => {
+                self.spawner.increase_score(points);
+                return Response::Observed;
             }
             Command::MoveOffset(movement) => self.offset.movement(movement),
             Command::Restart => {
                 self.actors.clear();
+                self.new_high_score = None;
+                self.game_over = false;
                 self.offset = Coordinates::default();
-                self.spawner.restart();
-                return false;
+                self.rewind.restart();
+                self.spawner
+                    .restart(self.seed.unwrap_or_else(seed_from_time));
+                return Response::Observed;
+            }
+            Command::Rewind => self.rewind.start(),
+            Command::SaveHighScore(initials) => self.save_high_score(initials),
+            _ => return Response::Ignored,
+        }
+        Response::Consumed
+    }
+
+    fn new_item(&mut self, item: Box<dyn GameItem>) -> Item {
+        let id = self.next_id;
+        self.next_id = id.next();
+        Item::new(id, item)
+    }
+
+    // Show the high scores, including the new score, if it qualifies.
+    fn prompt_for_initials(&mut self) {
+        let new_high_score = HighScore {
+            date: today(),
+            initials: String::new(),
+            level: self.spawner.level(),
+            score: self.spawner.score(),
+            seed: self.spawner.seed(),
+        };
+        if self.high_scores.rank(new_high_score.score).is_none() {
+            return;
+        }
+        let table = HighScoreTable::new(&self.high_scores, new_high_score.clone());
+        self.add_actor(Box::new(table));
+        self.new_high_score = Some(new_high_score);
+    }
+
+    fn save_high_score(&mut self, initials: Initials) {
+        if let Some(mut high_score) = self.new_high_score.take() {
+            high_score.initials = initials.iter().collect::<String>().trim_end().to_string();
+            self.high_scores.insert(high_score);
+            // Failing to save is not fatal, and is reported when the application exits, because it
+            // can't be while the TUI is active.
+            if let Err(error) = self.high_scores.save() {
+                self.save_errors.push(format!("{error:#}"));
             }
-            _ => return false,
         }
-        true
     }
 
     fn align_offset(&mut self, viewport: Viewport) {
@@ -150,59 +428,356 @@ impl World {
         self.offset.offset_x(dx);
     }
 
-    fn detect_collisions(&mut self) -> Vec<Command> {
-        let mut commands = Vec::new();
-        for index in 0..self.actors.len().saturating_sub(1) {
-            let (left_actors, right_actors) = self.actors.split_at_mut(index + 1);
-            let left_actor = &mut left_actors[index];
-            if left_actor.deleted() {
-                // Do not detect collisions on deleted actors,
-                // which can occur when the ship moves faster than once per tick.
+    // Each actor in a collision is told what it collided with, and the consequences are handled
+    // before the next collision, in which a destroyed actor can no longer take part.
+    fn detect_collisions(&mut self) {
+        let mut spatial_hash = SpatialHash::default();
+        for (index, actor) in self.actors.iter().enumerate() {
+            if !actor.deleted() && actor.kind().collides_with_anything() {
+                spatial_hash.insert(index, actor.viewport());
+            }
+        }
+
+        for (left_index, right_index) in spatial_hash.candidate_pairs() {
+            let left_actor = &self.actors[left_index];
+            let right_actor = &self.actors[right_index];
+            // Do not detect collisions on deleted actors,
+            // which can occur when the ship moves faster than once per tick.
+            if left_actor.deleted()
+                || right_actor.deleted()
+                || !left_actor.kind().collides_with(right_actor.kind())
+            {
                 continue;
             }
-            for right_actor in right_actors {
-                if right_actor.deleted() {
-                    continue;
-                }
-                if left_actor.viewport().intersects(right_actor.viewport()) {
-                    commands
-                        .extend(left_actor.handle_command(Command::Collide(right_actor.kind())));
-                    commands
-                        .extend(right_actor.handle_command(Command::Collide(left_actor.kind())));
-                }
+            if let Some((left_hitbox, right_hitbox)) = find_collision(left_actor, right_actor) {
+                let (left_id, left_kind) = (left_actor.id, left_actor.kind());
+                let (right_id, right_kind) = (right_actor.id, right_actor.kind());
+                self.dispatch(Event::addressed(
+                    right_id,
+                    left_id,
+                    Command::Collide(right_kind, left_hitbox),
+                ));
+                self.dispatch(Event::addressed(
+                    left_id,
+                    right_id,
+                    Command::Collide(left_kind, right_hitbox),
+                ));
             }
         }
-        commands
     }
 
-    fn game_items_iter_mut(&mut self) -> impl Iterator<Item = &mut Box<dyn GameItem>> {
+    fn game_items_iter_mut(&mut self) -> impl Iterator<Item = &mut Item> {
         self.actors.iter_mut().chain(self.ui.iter_mut())
     }
 
-    fn notify_handlers(&mut self, command: Command) -> Vec<Command> {
-        if self.consumed_command(command) {
-            return NO_COMMANDS;
+    fn rewind_step(&mut self) {
+        if let Some(snapshot) = self.rewind.step() {
+            self.actors = snapshot.actors;
+            self.offset = snapshot.offset;
+            self.spawner = snapshot.spawner;
+            self.rewind_ui(snapshot.ui);
         }
-        self.game_items_iter_mut()
-            .flat_map(move |handler| handler.handle_command(command))
-            .collect::<Vec<Command>>()
     }
 
-    fn notify_ui_handlers(&mut self, command: Command) -> Vec<Command> {
+    // Restore the UI items that existed when the snapshot was taken, which are matched by ID,
+    // because items can be added in the meantime, such as the game over alert. Items that aren't
+    // rewindable keep their current state.
+    fn rewind_ui(&mut self, previous_ui: Vec<(ItemId, Option<Item>)>) {
+        let mut current_ui = mem::take(&mut self.ui);
+        self.ui = previous_ui
+            .into_iter()
+            .filter_map(|(id, previous_item)| {
+                previous_item.or_else(|| {
+                    let index = current_ui.iter().position(|item| item.id == id)?;
+                    Some(current_ui.remove(index))
+                })
+            })
+            .collect();
         self.ui
-            .iter_mut()
-            .flat_map(move |handler| handler.handle_command(command))
-            .collect::<Vec<Command>>()
+            .extend(current_ui.into_iter().filter(|item| !item.rewindable()));
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            actors: self.actors.clone(),
+            offset: self.offset,
+            spawner: self.spawner.clone(),
+            ui: self
+                .ui
+                .iter()
+                .map(|item| (item.id, item.rewindable().then(|| item.clone())))
+                .collect(),
+        }
     }
 }
 
-fn validate_unhandled_commands(commands: &[Command]) -> Result<()> {
-    if commands.is_empty() {
-        Ok(())
-    } else {
-        Err(anyhow!(
-            "Error: There are unhandled command(s): {:?}",
-            commands
-        ))
+// Return the indices of the first pair of intersecting hitboxes, if any.
+fn find_collision(left: &Item, right: &Item) -> Option<(usize, usize)> {
+    // Most actors don't intersect, so first check whether their outer viewports do.
+    if !left.viewport().intersects(right.viewport()) {
+        return None;
+    }
+    let right_hitboxes = right.hitboxes();
+    left.hitboxes()
+        .iter()
+        .enumerate()
+        .find_map(|(left_index, left_hitbox)| {
+            right_hitboxes
+                .iter()
+                .position(|right_hitbox| left_hitbox.intersects(*right_hitbox))
+                .map(|right_index| (left_index, right_index))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        app::{
+            command::{CommandHandler, CommandKind},
+            main::TICKS_PER_SECOND,
+        },
+        clock::ticker::TickHandler,
+        game::{
+            game_item::{GameItemKind, SavedGameItem},
+            random::Random,
+            spawner::levels::builtin_levels,
+            ui::boss_health_bar::BossHealthBar,
+        },
+        view::{
+            factory::{WORLD_HEIGHT, WORLD_WIDTH},
+            render::Renderable,
+            renderer::Renderer,
+        },
+    };
+    use std::{
+        env, fs, process,
+        time::{Duration, Instant},
+    };
+
+    const BENCHMARK_ITERATIONS: u32 = 100;
+    const MAX_COLLISIONS_DURATION: Duration = Duration::from_millis(20); // The main loop's budget
+
+    fn new_world() -> World {
+        World::for_test(0)
+    }
+
+    // Returns a command whenever it receives the command that it subscribes to.
+    #[derive(Clone)]
+    struct Echo(Command);
+
+    impl CommandHandler for Echo {
+        fn handle_command(&mut self, command: Command) -> Vec<Command> {
+            vec![command, self.0]
+        }
+    }
+
+    impl GameItem for Echo {
+        fn saved(&self) -> SavedGameItem {
+            unreachable!()
+        }
+
+        fn subscriptions(&self) -> &'static [CommandKind] {
+            &[CommandKind::FireGuns]
+        }
+    }
+
+    impl Renderable for Echo {
+        fn render(&self, _: &mut Renderer) {}
+
+        fn viewport(&self) -> Viewport {
+            Viewport::new(1, 1)
+        }
+    }
+
+    impl TickHandler for Echo {}
+
+    #[test]
+    fn detect_collisions_reports_overlapping_actors() {
+        let mut world = new_world();
+        world.add_actor(Box::new(Asteroid::new_small(Coordinates::new(199, 10))));
+        world.add_actor(Box::new(Bullet::new(Coordinates::new(0, 11))));
+        world.add_actor(Box::new(Bullet::new(Coordinates::new(50, 10))));
+
+        world.detect_collisions();
+
+        assert!(world.actors[1].deleted());
+        assert!(!world.actors[2].deleted());
+        assert_eq!(world.actors.len(), 3); // The asteroid is damaged, but not destroyed
+    }
+
+    #[test]
+    fn addressed_commands_are_only_delivered_to_their_target() {
+        let mut world = new_world();
+        world.add_actor(Box::new(Bullet::new(Coordinates::new(10, 10))));
+        world.add_actor(Box::new(Bullet::new(Coordinates::new(20, 10))));
+        let (first, second) = (world.actors[0].id, world.actors[1].id);
+
+        world.dispatch(Event::addressed(
+            first,
+            second,
+            Command::Collide(GameItemKind::Asteroid, 0),
+        ));
+
+        assert!(!world.actors[0].deleted());
+        assert!(world.actors[1].deleted());
+    }
+
+    #[test]
+    fn unhandled_commands_are_recorded() {
+        let mut world = new_world();
+        world.add_actor(Box::new(Bullet::new(Coordinates::new(10, 10))));
+        let id = world.actors[0].id;
+
+        world.broadcast_commands(&[Command::MenuUp, Command::MenuUp]);
+        world.dispatch(Event::addressed(
+            id,
+            id.next(),
+            Command::Collide(GameItemKind::Bullet, 0),
+        ));
+
+        assert_eq!(
+            world.diagnostics().report(),
+            [
+                "unhandled event: MenuUp (2 times)",
+                "unhandled event: Collide(Bullet, 0) to #1 from #0, whose target doesn't exist"
+            ]
+        );
+    }
+
+    #[test]
+    fn cycles_of_commands_are_dropped() {
+        let mut world = new_world();
+        world.add_actor(Box::new(Echo(Command::IncreaseScore(1))));
+
+        world.broadcast_commands(&[Command::FireGuns]);
+
+        // The echoed score is counted once, and the echoed FireGuns isn't delivered again.
+        assert_eq!(world.score(), 1);
+        assert_eq!(
+            world.diagnostics().report(),
+            ["dropped a cycle of events: FireGuns -> FireGuns from #0"]
+        );
+    }
+
+    #[test]
+    fn failing_to_save_a_high_score_is_recorded() {
+        // The high scores can't be saved in a directory whose path is a file.
+        let file = env::temp_dir().join(format!("space_time_rewind_{}_file", process::id()));
+        fs::write(&file, "").unwrap();
+        let path = file.join("high_scores.toml");
+        let high_scores = HighScores::load(Some(path), &mut Vec::new());
+        let mut world = World::for_test(0).with_high_scores(high_scores);
+        world.broadcast_commands(&[Command::IncreaseScore(10), Command::GameOver]);
+
+        world.save_high_score(['A', 'B', 'C']);
+        fs::remove_file(&file).unwrap();
+
+        assert_eq!(world.high_scores().scores().len(), 1);
+        let errors = world.save_errors();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with(&format!("cannot create directory: {}: ", file.display())));
+    }
+
+    #[test]
+    fn restart_clears_game_over() {
+        let mut world = new_world();
+        world.broadcast_commands(&[Command::GameOver]);
+
+        assert!(world.game_over());
+        assert!(!world.entering_initials()); // A score of 0 doesn't qualify
+
+        world.broadcast_commands(&[Command::Restart]);
+        assert!(!world.game_over());
+    }
+
+    #[test]
+    fn rewind_history_follows_the_rewind_seconds() {
+        // Play for longer than the window, then count the ticks that the rewind lasts.
+        let rewinding_ticks = |rewind_seconds| {
+            let mut world = World::new(
+                Some(0),
+                builtin_levels().into(),
+                HighScores::default(),
+                Rc::default(),
+                rewind_seconds,
+            );
+            let mut ticker = Ticker::new(Duration::ZERO);
+            world.broadcast_commands(&[Command::Restart]);
+            for _ in 0..u64::from(TICKS_PER_SECOND) * 10 {
+                ticker.tick();
+                world.handle_tick(&ticker);
+            }
+            world.broadcast_commands(&[Command::Rewind]);
+            let mut ticks = 0;
+            while world.rewinding() {
+                ticker.tick();
+                world.handle_tick(&ticker);
+                ticks += 1;
+            }
+            ticks
+        };
+
+        assert!(rewinding_ticks(1) > 0);
+        assert_eq!(rewinding_ticks(2), rewinding_ticks(1) * 2);
+        assert_eq!(rewinding_ticks(5), rewinding_ticks(1) * 5);
+    }
+
+    #[test]
+    fn rewind_restores_the_ui_items_by_id() {
+        let mut world = new_world();
+        let mut ticker = Ticker::new(Duration::ZERO);
+        world.broadcast_commands(&[Command::Restart]);
+        ticker.tick();
+        world.handle_tick(&ticker);
+        let ids: Vec<ItemId> = world.ui.iter().map(|item| item.id).collect();
+
+        // A boss health bar is added in front of the others during the rewind window.
+        let bar = world.new_item(Box::new(BossHealthBar::default()));
+        world.ui.insert(0, bar);
+        world.broadcast_commands(&[Command::UpdateBossHealth(10, 40)]);
+        for _ in 0..3 {
+            ticker.tick();
+            world.handle_tick(&ticker);
+        }
+        world.broadcast_commands(&[Command::Rewind]);
+        while world.rewinding() {
+            ticker.tick();
+            world.handle_tick(&ticker);
+        }
+
+        let rewound_ids: Vec<ItemId> = world.ui.iter().map(|item| item.id).collect();
+        assert_eq!(rewound_ids, ids);
+    }
+
+    // Run with: cargo test --release -- --ignored --nocapture benchmark
+    #[test]
+    #[ignore]
+    fn benchmark_detect_collisions_with_1000_actors() {
+        let mut random = Random::new(0);
+        let mut world = new_world();
+        for i in 0..1000 {
+            let x = u8::try_from(random.below(u64::from(WORLD_WIDTH))).unwrap();
+            let y = i8::try_from(random.below(u64::from(WORLD_HEIGHT))).unwrap();
+            let coordinates = Coordinates::new(x, y);
+            match i % 4 {
+                0 => world.add_actor(Box::new(Asteroid::new_small(coordinates))),
+                1 => world.add_actor(Box::new(Explosion::new(coordinates))),
+                _ => world.add_actor(Box::new(Bullet::new(coordinates))),
+            }
+        }
+
+        let actors = world.actors.clone();
+        let mut elapsed = Duration::ZERO;
+        for _ in 0..BENCHMARK_ITERATIONS {
+            world.actors = actors.clone();
+            let started = Instant::now();
+            world.detect_collisions();
+            elapsed += started.elapsed();
+        }
+
+        let average = elapsed / BENCHMARK_ITERATIONS;
+        println!("detect_collisions() with 1000 actors took {average:?} on average");
+        assert!(average < MAX_COLLISIONS_DURATION);
     }
 }
@@ -1,16 +1,45 @@
 use anyhow::Result;
-use app::main::App;
+use app::{main::App, options::Options};
+use clock::time::SystemClock;
 use view::session::Session;
 
+pub use app::{
+    command::Command,
+    headless::{Headless, Report, ScriptedCommand},
+};
+pub use game::game_item::GameItemKind;
+
 mod app;
 mod clock;
 mod game;
 mod view;
 
 pub fn run() -> Result<()> {
+    let options = Options::from_args()?;
+    if options.help {
+        println!("{}", Options::usage());
+        return Ok(());
+    }
+
+    let mut app = App::new(&options, Box::new(SystemClock::default()))?;
     let mut session = Session::begin()?;
 
-    App::default().run(&mut session)?;
+    // End the session before reporting any error, so that the terminal is restored.
+    let result = app.run(&mut session);
+    session.end()?;
+    for message in app.file_errors() {
+        eprintln!("{message}");
+    }
+    if cfg!(debug_assertions) {
+        for message in app.diagnostics() {
+            eprintln!("{message}");
+        }
+    }
+    result?;
 
-    Ok(session.end()?)
+    if let Some(result) = app.take_replay_result() {
+        result?;
+        println!("The replay ended in the same way as the recording");
+    }
+    Ok(())
 }
@@ -1,7 +1,12 @@
+pub mod animation;
 pub mod coordinates;
-mod factory;
+pub mod factory;
 pub mod render;
 pub mod renderer;
 pub mod session;
+#[cfg(test)]
+mod snapshot;
+pub mod sprite;
 pub mod util;
+pub mod velocity;
 pub mod viewport;
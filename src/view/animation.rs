@@ -0,0 +1,194 @@
+// Animations show a sequence of a sprite's frames, each in its own color for a number of ticks.
+// They're advanced by the actor that owns them, so that they're saved and rewound along with it.
+use super::sprite::{self, Sprite};
+use crate::app::color::ColorTheme;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Playback {
+    Loop,     // Restart from the first frame after the last one
+    Once,     // Stop at the last frame
+    PingPong, // Play backwards from the last frame, then forwards again from the first one
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Frame {
+    color: ColorTheme,
+    index: usize, // Of the frame in the sprite's file
+    ticks: u16,   // How long the frame is shown
+}
+
+impl Frame {
+    pub fn new(index: usize, color: ColorTheme, ticks: u16) -> Self {
+        Self {
+            color,
+            index,
+            ticks: ticks.max(1),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Animation {
+    current: usize, // Index into `frames`
+    finished: bool,
+    frames: Vec<Frame>,
+    playback: Playback,
+    reversed: bool, // While a ping-pong animation plays backwards
+    sprite: Sprite,
+    ticks_left: u16,
+}
+
+impl Animation {
+    pub fn new(sprite: Sprite, playback: Playback, frames: Vec<Frame>) -> Self {
+        assert!(!frames.is_empty(), "An animation has at least one frame");
+        debug_assert!(
+            frames.iter().all(|frame| frame.index < sprite.frames()),
+            "{sprite:?} doesn't have enough frames for its animation"
+        );
+        Self {
+            current: 0,
+            finished: false,
+            ticks_left: frames[0].ticks,
+            frames,
+            playback,
+            reversed: false,
+            sprite,
+        }
+    }
+
+    pub fn color(&self) -> ColorTheme {
+        self.frame().color
+    }
+
+    pub fn text(&self) -> &'static str {
+        sprite::frame(self.sprite, self.frame().index)
+    }
+
+    // Move on to the next frame once the current one has been shown for long enough. `on_complete`
+    // is called when a `Once` animation finishes, and whenever the others complete a cycle.
+    pub fn handle_tick(&mut self, on_complete: impl FnOnce()) {
+        if self.finished {
+            return;
+        }
+        self.ticks_left = self.ticks_left.saturating_sub(1);
+        if self.ticks_left > 0 {
+            return;
+        }
+        let completed = self.advance();
+        self.ticks_left = self.frame().ticks;
+        if completed {
+            on_complete();
+        }
+    }
+
+    // Returns whether the animation completed.
+    fn advance(&mut self) -> bool {
+        let last = self.frames.len() - 1;
+        match self.playback {
+            Playback::Loop if self.current == last => {
+                self.current = 0;
+                true
+            }
+            Playback::Once if self.current == last => {
+                self.finished = true;
+                true
+            }
+            Playback::Loop | Playback::Once => {
+                self.current += 1;
+                false
+            }
+            Playback::PingPong if last == 0 => true,
+            Playback::PingPong => {
+                if self.reversed {
+                    self.current -= 1;
+                } else {
+                    self.current += 1;
+                }
+                if self.current == last {
+                    self.reversed = true;
+                }
+                let completed = self.current == 0;
+                if completed {
+                    self.reversed = false;
+                }
+                completed
+            }
+        }
+    }
+
+    fn frame(&self) -> Frame {
+        self.frames[self.current]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_animation(playback: Playback, ticks: &[u16]) -> Animation {
+        let frames = ticks
+            .iter()
+            .map(|ticks| Frame::new(0, ColorTheme::Ship, *ticks))
+            .collect();
+        Animation::new(Sprite::Ship, playback, frames)
+    }
+
+    // The index into `frames` after each tick, and the ticks after which `on_complete` was called.
+    fn play(animation: &mut Animation, ticks: u16) -> (Vec<usize>, Vec<u16>) {
+        let mut currents = Vec::new();
+        let mut completions = Vec::new();
+        for tick in 1..=ticks {
+            animation.handle_tick(|| completions.push(tick));
+            currents.push(animation.current);
+        }
+        (currents, completions)
+    }
+
+    #[test]
+    fn frames_are_shown_for_their_number_of_ticks() {
+        let mut animation = new_animation(Playback::Loop, &[2, 1, 3]);
+
+        assert_eq!(
+            play(&mut animation, 8),
+            (vec![0, 1, 2, 2, 2, 0, 0, 1], vec![6])
+        );
+    }
+
+    #[test]
+    fn once_stops_at_the_last_frame() {
+        let mut animation = new_animation(Playback::Once, &[1, 1, 1]);
+
+        assert_eq!(play(&mut animation, 5), (vec![1, 2, 2, 2, 2], vec![3]));
+        assert!(animation.finished);
+    }
+
+    #[test]
+    fn ping_pong_reverses_at_each_end() {
+        let mut animation = new_animation(Playback::PingPong, &[1, 1, 1]);
+
+        assert_eq!(play(&mut animation, 6), (vec![1, 2, 1, 0, 1, 2], vec![4]));
+    }
+
+    #[test]
+    fn a_single_frame_completes_after_its_ticks() {
+        for playback in [Playback::Loop, Playback::PingPong] {
+            let mut animation = new_animation(playback, &[2]);
+
+            assert_eq!(play(&mut animation, 4), (vec![0, 0, 0, 0], vec![2, 4]));
+        }
+    }
+
+    #[test]
+    fn color_and_text_follow_the_current_frame() {
+        let frames = vec![
+            Frame::new(0, ColorTheme::Ship, 1),
+            Frame::new(1, ColorTheme::ShipShields, 1),
+        ];
+        let mut animation = Animation::new(Sprite::Ship, Playback::Loop, frames);
+        animation.handle_tick(|| ());
+
+        assert_eq!(animation.color(), ColorTheme::ShipShields);
+        assert_eq!(animation.text(), sprite::frame(Sprite::Ship, 1));
+    }
+}
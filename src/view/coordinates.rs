@@ -1,8 +1,9 @@
 use super::factory::{WORLD_HEIGHT, WORLD_WIDTH};
+use serde::{Deserialize, Serialize};
 
 pub type Movement = (i16, i16);
 
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Coordinates(u8, i8);
 
 impl Coordinates {
@@ -1,10 +1,10 @@
-use super::viewport::Viewport;
-use crate::app::color::ColorTheme;
+use super::{util::chars_width, viewport::Viewport};
+use crate::app::{color::ColorTheme, menu::Menu};
 use tui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     symbols::Marker,
-    text::Span,
+    text::{Span, Spans},
     widgets::{
         canvas::{Canvas, Context},
         Block, BorderType, Borders, Paragraph, Wrap,
@@ -21,7 +21,10 @@ pub const WINDOW_MIN_WIDTH: u8 = 19;
 pub const WORLD_HEIGHT: u8 = MAX_HEIGHT - UI_HEIGHT - 2; // Account for the actors viewport's borders
 pub const WORLD_WIDTH: u8 = 200; // The following must hold: WORLD_WIDTH + widest_actor < 256.
 
-static TITLE: &str = "Space-Time-Rewind!";
+pub static TITLE: &str = "Space-Time-Rewind!";
+const MENU_PADDING_WIDTH: u8 = 6; // Including the borders
+const MENU_SELECTION_WIDTH: u8 = 4; // The "> " and " <" around the selected item
+
 static RESIZE_WARNING_MESSAGE: &str = "Please increase the size of the terminal window";
 
 pub fn create_actors_block<'a>() -> Block<'a> {
@@ -33,15 +36,73 @@ pub fn create_background_block<'a>() -> Block<'a> {
     Block::default().style(Style::default().bg(Color::from(ColorTheme::Bg)))
 }
 
+pub fn create_menu_paragraph(menu: &Menu) -> Paragraph<'_> {
+    let title = Span::styled(
+        menu.title(),
+        Style::from(ColorTheme::MenuTitle).add_modifier(Modifier::BOLD),
+    );
+    let block = with_default_borders(create_background_block()).title(title);
+    let text_style = Style::from(ColorTheme::MenuText);
+    let mut lines: Vec<Spans> = menu
+        .body()
+        .iter()
+        .map(|line| Spans::from(Span::styled(line.as_str(), text_style)))
+        .collect();
+    if !lines.is_empty() {
+        lines.push(Spans::default());
+    }
+    for (item, label) in menu.entries() {
+        let spans = if item == menu.selected() {
+            Span::styled(
+                format!("> {label} <"),
+                Style::from(ColorTheme::MenuItemSelected).add_modifier(Modifier::BOLD),
+            )
+        } else {
+            Span::styled(label.to_string(), Style::from(ColorTheme::MenuItem))
+        };
+        lines.push(Spans::from(spans));
+    }
+    Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+}
+
+// A rect that's large enough for the menu, in the middle of `rect`.
+pub fn create_menu_rect(menu: &Menu, rect: Rect) -> Rect {
+    let widest_item = menu
+        .entries()
+        .map(|(_, label)| chars_width(label) + MENU_SELECTION_WIDTH)
+        .max()
+        .unwrap_or_default();
+    let widest_line = menu
+        .body()
+        .iter()
+        .map(|line| chars_width(line))
+        .max()
+        .unwrap_or_default();
+    let width = widest_item.max(widest_line).max(chars_width(menu.title())) + MENU_PADDING_WIDTH;
+    let body_height = if menu.body().is_empty() {
+        0
+    } else {
+        menu.body().len() + 1 // Separate the body from the items
+    };
+    let height = body_height + menu.len() + 2; // Account for the borders
+
+    let width = u16::from(width).min(rect.width);
+    let height = u16::try_from(height).unwrap().min(rect.height);
+    Rect {
+        x: rect.x + (rect.width - width) / 2,
+        y: rect.y + (rect.height - height) / 2,
+        width,
+        height,
+    }
+}
+
 pub fn create_resize_warning_paragraph<'a>() -> Paragraph<'a> {
     let title = create_title(ColorTheme::ErrorFg);
     let block = with_error_borders(create_background_block()).title(title);
     Paragraph::new(RESIZE_WARNING_MESSAGE)
-        .style(
-            Style::default()
-                .bg(Color::from(ColorTheme::Bg))
-                .fg(Color::from(ColorTheme::ErrorFg)),
-        )
+        .style(Style::from(ColorTheme::ErrorFg).bg(Color::from(ColorTheme::Bg)))
         .block(block)
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true })
@@ -94,7 +155,7 @@ pub fn split_into_actors_and_ui(rect: Rect) -> (Rect, Rect) {
 }
 
 fn create_title<'a>(color: ColorTheme) -> Span<'a> {
-    Span::styled(TITLE, Style::default().fg(Color::from(color)))
+    Span::styled(TITLE, Style::from(color))
 }
 
 fn normalize(rect: Rect) -> Rect {
@@ -114,13 +175,13 @@ fn normalize(rect: Rect) -> Rect {
 fn with_default_borders(block: Block) -> Block {
     block
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::from(ColorTheme::BoardBorderFg)))
+        .border_style(Style::from(ColorTheme::BoardBorderFg))
         .border_type(BorderType::Rounded)
 }
 
 fn with_error_borders(block: Block) -> Block {
     block
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::from(ColorTheme::ErrorFg)))
+        .border_style(Style::from(ColorTheme::ErrorFg))
         .border_type(BorderType::Rounded)
 }
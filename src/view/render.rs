@@ -7,21 +7,20 @@ use super::{
     viewport::Viewport,
 };
 use crate::{
-    game::{game_item::GameItem, world::World},
-    view::{
-        factory::{
-            create_actors_block, create_actors_viewport, create_background_block, create_ui_block,
-            create_ui_viewport, split_into_actors_and_ui,
-        },
-        session::Session,
+    app::menu::Menu,
+    game::{event_bus::Item, world::World},
+    view::factory::{
+        create_actors_block, create_actors_viewport, create_background_block,
+        create_menu_paragraph, create_menu_rect, create_ui_block, create_ui_viewport,
+        split_into_actors_and_ui,
     },
 };
 use anyhow::Result;
 use tui::{
     backend::Backend,
     layout::Rect,
-    widgets::{canvas::Context, Block},
-    Frame,
+    widgets::{canvas::Context, Block, Clear},
+    Frame, Terminal,
 };
 
 const MIN_CANVAS_LENGTH: u16 = 3;
@@ -32,8 +31,12 @@ pub trait Renderable {
     fn viewport(&self) -> Viewport;
 }
 
-pub fn render(session: &mut Session, world: &mut World) -> Result<()> {
-    session.terminal.draw(|frame| {
+pub fn render<B: Backend>(
+    terminal: &mut Terminal<B>,
+    world: &mut World,
+    menu: Option<&Menu>,
+) -> Result<()> {
+    terminal.draw(|frame| {
         let window = frame.size();
         let (actors_rect, ui_rect) = split_into_actors_and_ui(window);
 
@@ -42,14 +45,7 @@ pub fn render(session: &mut Session, world: &mut World) -> Result<()> {
             return;
         }
 
-        let actors_viewport = create_actors_viewport(actors_rect);
-        let ui_viewport = create_ui_viewport(ui_rect);
-        world
-            .broadcast_actors_viewport(actors_viewport)
-            .expect("Broadcast Actors Viewport succeeds");
-        world
-            .broadcast_ui_viewport(ui_viewport)
-            .expect("Broadcast UI Viewport succeeds");
+        let (actors_viewport, ui_viewport) = broadcast_viewports(world, window);
 
         render_background(frame);
         render_canvas(
@@ -68,10 +64,23 @@ pub fn render(session: &mut Session, world: &mut World) -> Result<()> {
             ui_rect,
             ui_viewport,
         );
+        if let Some(menu) = menu {
+            render_menu(frame, menu, actors_rect);
+        }
     })?;
     Ok(())
 }
 
+// Tell the World the sizes of the actors and the UI within the window, without drawing them.
+pub fn broadcast_viewports(world: &mut World, window: Rect) -> (Viewport, Viewport) {
+    let (actors_rect, ui_rect) = split_into_actors_and_ui(window);
+    let actors_viewport = create_actors_viewport(actors_rect);
+    let ui_viewport = create_ui_viewport(ui_rect);
+    world.broadcast_actors_viewport(actors_viewport);
+    world.broadcast_ui_viewport(ui_viewport);
+    (actors_viewport, ui_viewport)
+}
+
 fn render_background<B: Backend>(frame: &mut Frame<B>) {
     // Set the background color of the *entire* terminal window, even outside of the canvas'.
     frame.render_widget(create_background_block(), frame.size());
@@ -79,7 +88,7 @@ fn render_background<B: Backend>(frame: &mut Frame<B>) {
 
 fn render_canvas<B: Backend>(
     frame: &mut Frame<B>,
-    renderables: &[Box<dyn GameItem>],
+    renderables: &[Item],
     block: Block,
     offset: Coordinates,
     rect: Rect,
@@ -98,6 +107,13 @@ fn render_canvas<B: Backend>(
     frame.render_widget(canvas, rect);
 }
 
+// Render the menu over the middle of the actors.
+fn render_menu<B: Backend>(frame: &mut Frame<B>, menu: &Menu, rect: Rect) {
+    let rect = create_menu_rect(menu, rect);
+    frame.render_widget(Clear, rect);
+    frame.render_widget(create_menu_paragraph(menu), rect);
+}
+
 fn is_canvas_too_small_to_render(canvas: Rect) -> bool {
     canvas.width < MIN_CANVAS_LENGTH || canvas.height < MIN_CANVAS_LENGTH
 }
@@ -3,7 +3,7 @@ use super::{
 };
 use crate::app::color::ColorTheme;
 use tui::{
-    style::{Color, Style},
+    style::Style,
     text::{Span, Spans},
     widgets::canvas::Context,
 };
@@ -23,16 +23,16 @@ impl<'a, 'b> Renderer<'a, 'b> {
         }
     }
 
-    pub fn render(&mut self, viewport: Viewport, text: &'static str, color: ColorTheme) {
+    pub fn render(&mut self, viewport: Viewport, text: &str, color: ColorTheme) {
         if self.is_not_visible(viewport) {
             return;
         }
 
-        let style = Style::default().fg(Color::from(color));
+        let style = Style::from(color);
         let (x, y) = viewport.bottom_left().as_tuple();
         for (y_offset, line) in enumerate_reversed(text) {
             let y_offset = i8::try_from(y_offset).unwrap();
-            let span = Span::styled(line, style);
+            let span = Span::styled(line.to_string(), style);
             self.print(x, y + y_offset, Spans::from(span));
         }
     }
@@ -48,7 +48,7 @@ impl<'a, 'b> Renderer<'a, 'b> {
             return;
         }
 
-        let style = Style::default().fg(Color::from(color));
+        let style = Style::from(color);
 
         let (x, y) = viewport.bottom_left().as_tuple();
         let x_offset = offset_x(x, text);
@@ -74,6 +74,10 @@ impl<'a, 'b> Renderer<'a, 'b> {
         self.print(x, y, Spans::from(spans));
     }
 
+    pub fn viewport(&self) -> Viewport {
+        self.viewport
+    }
+
     fn print(&mut self, x: u8, y: i8, spans: Spans<'b>) {
         let x = f64::from(x);
         let y = f64::from(y);
@@ -96,6 +100,7 @@ fn enumerate_reversed(text: &str) -> impl Iterator<Item = (usize, &str)> + '_ {
     text.lines().rev().enumerate()
 }
 
+#[allow(clippy::unnecessary_fallible_conversions)]
 fn offset_x(x: u8, s: &str) -> usize {
     let width = chars_width(s);
     let x_offset = WORLD_WIDTH - x;
@@ -1,4 +1,5 @@
 use crossterm::{
+    event::{DisableFocusChange, EnableFocusChange},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -17,10 +18,13 @@ impl Session {
 
         let mut terminal = create_terminal()?;
         terminal.hide_cursor()?;
+        // Focus events are always reported, because auto-pause can be enabled from the settings menu.
+        execute!(terminal.backend_mut(), EnableFocusChange)?;
         Ok(Self { terminal })
     }
 
     pub fn end(&mut self) -> Result<()> {
+        execute!(self.terminal.backend_mut(), DisableFocusChange)?;
         execute!(self.terminal.backend_mut(), LeaveAlternateScreen)?;
         self.terminal.show_cursor()?;
         disable_raw_mode()?;
@@ -0,0 +1,307 @@
+// Snapshot tests, which render a World into an in-memory terminal and compare what was drawn to the
+// golden files in `src/view/snapshots/`. Run `BLESS_SNAPSHOTS=1 cargo test` to (re)write them after
+// an intentional change, and review the differences before committing them.
+use super::{coordinates::Coordinates, render::render};
+use crate::{
+    app::{
+        color::{set_mode, set_palette, ColorMode, Palette},
+        command::Command,
+        menu::{Menu, MenuItem},
+    },
+    clock::ticker::Ticker,
+    game::{
+        actors::{
+            asteroid::Asteroid, boss::Boss, bullet::Bullet, enemy::Enemy, explosion::Explosion,
+            high_score_table::HighScoreTable, level_banner::LevelBanner, missile::Missile,
+            power_up::PowerUp, ship::Ship,
+        },
+        game_item::GameItem,
+        high_scores::{HighScore, HighScores},
+        world::World,
+    },
+    view::factory::TITLE,
+};
+use std::{env, fs, path::PathBuf, time::Duration};
+use tui::{
+    backend::TestBackend,
+    buffer::Buffer,
+    style::{Color, Style},
+    Terminal,
+};
+
+static BLESS_VARIABLE: &str = "BLESS_SNAPSHOTS";
+// Each style in a snapshot is represented by one of these characters.
+static STYLE_KEYS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+const SIZE: (u16, u16) = (60, 16);
+
+// A World that only contains the given actors, without the UI.
+fn world_with_actors(actors: Vec<Box<dyn GameItem>>) -> World {
+    let mut world = World::for_test(0);
+    for actor in actors {
+        world.add_actor(actor);
+    }
+    world
+}
+
+// A World after the first tick of a new game, which spawns the ship and the UI.
+fn started_world(high_scores: HighScores) -> World {
+    let mut world = World::for_test(0).with_high_scores(high_scores);
+    let mut ticker = Ticker::new(Duration::ZERO);
+    world.broadcast_commands(&[Command::Restart]);
+    ticker.tick();
+    world.handle_tick(&ticker);
+    world
+}
+
+// Render with the default colors, regardless of which theme or color mode a previous test used.
+fn render_world(world: &mut World, (width, height): (u16, u16), menu: Option<&Menu>) -> Buffer {
+    set_mode(ColorMode::TrueColor);
+    set_palette(Palette::new());
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    render(&mut terminal, world, menu).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+fn assert_snapshot(name: &str, buffer: &Buffer) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/view/snapshots")
+        .join(format!("{name}.txt"));
+    let actual = to_text(buffer);
+    if env::var_os(BLESS_VARIABLE).is_some() {
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "missing snapshot: {}, run the tests with {BLESS_VARIABLE}=1 to create it",
+            path.display()
+        )
+    });
+    assert!(
+        actual == expected,
+        "snapshot {name} differs, run the tests with {BLESS_VARIABLE}=1 to update it\n\
+         --- expected:\n{expected}\n--- actual:\n{actual}"
+    );
+}
+
+// The glyphs, then a key for the style of each cell, then the style that each key stands for.
+// Trailing spaces are trimmed from the glyphs, because editors tend to remove them, but the styles
+// of those cells are still compared.
+fn to_text(buffer: &Buffer) -> String {
+    let mut styles: Vec<Style> = Vec::new();
+    let mut glyphs = String::new();
+    let mut keys = String::new();
+    for y in 0..buffer.area.height {
+        let mut line = String::new();
+        for x in 0..buffer.area.width {
+            let cell = buffer.get(x, y);
+            line.push_str(&cell.symbol);
+            let style = cell.style();
+            let index = styles
+                .iter()
+                .position(|other| *other == style)
+                .unwrap_or_else(|| {
+                    styles.push(style);
+                    styles.len() - 1
+                });
+            keys.push(
+                STYLE_KEYS
+                    .chars()
+                    .nth(index)
+                    .expect("A snapshot has at most 62 styles"),
+            );
+        }
+        glyphs.push_str(line.trim_end());
+        glyphs.push('\n');
+        keys.push('\n');
+    }
+    let legend: String = STYLE_KEYS
+        .chars()
+        .zip(&styles)
+        .map(|(key, style)| format!("{key}: {}\n", describe_style(*style)))
+        .collect();
+    format!("{glyphs}\n{keys}\n{legend}")
+}
+
+// E.g. `fg=#4a9ee6 bg=#151515 BOLD`.
+fn describe_style(style: Style) -> String {
+    let mut description = format!(
+        "fg={} bg={}",
+        describe_color(style.fg.unwrap_or(Color::Reset)),
+        describe_color(style.bg.unwrap_or(Color::Reset))
+    );
+    if !style.add_modifier.is_empty() {
+        description.push_str(&format!(" {:?}", style.add_modifier));
+    }
+    description
+}
+
+fn describe_color(color: Color) -> String {
+    match color {
+        Color::Rgb(r, g, b) => format!("#{r:02x}{g:02x}{b:02x}"),
+        Color::Indexed(index) => format!("{index}"),
+        _ => format!("{color:?}").to_lowercase(),
+    }
+}
+
+fn high_score(score: u32) -> HighScore {
+    HighScore {
+        date: String::from("2024-01-01"),
+        initials: String::from("ABC"),
+        level: 2,
+        score,
+        seed: 7,
+    }
+}
+
+// Scores that a new game's score of 0 doesn't qualify for.
+fn full_high_scores() -> HighScores {
+    let mut high_scores = HighScores::default();
+    for score in [500, 400, 300, 200, 100] {
+        high_scores.insert(high_score(score));
+    }
+    high_scores
+}
+
+#[test]
+fn asteroids() {
+    let mut world = world_with_actors(vec![
+        Box::new(Asteroid::new_large(Coordinates::new(2, 2))),
+        Box::new(Asteroid::new_medium(Coordinates::new(28, 4))),
+        Box::new(Asteroid::new_small(Coordinates::new(44, 6))),
+    ]);
+
+    assert_snapshot("asteroids", &render_world(&mut world, SIZE, None));
+}
+
+#[test]
+fn boss() {
+    let mut world = world_with_actors(vec![Box::new(Boss::new(Coordinates::new(20, 3), 30))]);
+
+    assert_snapshot("boss", &render_world(&mut world, SIZE, None));
+}
+
+#[test]
+fn bullets() {
+    let mut world = world_with_actors(vec![
+        Box::new(Bullet::new(Coordinates::new(20, 3))),
+        Box::new(Bullet::new_enemy(Coordinates::new(30, 6), 0)),
+    ]);
+
+    assert_snapshot("bullets", &render_world(&mut world, SIZE, None));
+}
+
+#[test]
+fn enemies() {
+    let mut world = world_with_actors(vec![
+        Box::new(Enemy::new_fighter(Coordinates::new(10, 4))),
+        Box::new(Enemy::new_strafer(Coordinates::new(30, 4))),
+    ]);
+
+    assert_snapshot("enemies", &render_world(&mut world, SIZE, None));
+}
+
+#[test]
+fn explosion() {
+    let mut world = world_with_actors(vec![Box::new(Explosion::new(Coordinates::new(25, 3)))]);
+
+    assert_snapshot("explosion", &render_world(&mut world, SIZE, None));
+}
+
+#[test]
+fn high_score_table() {
+    let table = HighScoreTable::new(&full_high_scores(), high_score(250));
+    let mut world = world_with_actors(vec![Box::new(table)]);
+
+    assert_snapshot("high_score_table", &render_world(&mut world, SIZE, None));
+}
+
+#[test]
+fn level_banner() {
+    let mut world = world_with_actors(vec![Box::new(LevelBanner::new(3))]);
+
+    assert_snapshot("level_banner", &render_world(&mut world, SIZE, None));
+}
+
+#[test]
+fn missile() {
+    let mut world = world_with_actors(vec![Box::new(Missile::new(Coordinates::new(25, 2)))]);
+
+    assert_snapshot("missile", &render_world(&mut world, SIZE, None));
+}
+
+#[test]
+fn power_ups() {
+    let mut world = world_with_actors(vec![
+        Box::new(PowerUp::new_health(Coordinates::new(15, 4))),
+        Box::new(PowerUp::new_missile(Coordinates::new(35, 4))),
+    ]);
+
+    assert_snapshot("power_ups", &render_world(&mut world, SIZE, None));
+}
+
+#[test]
+fn ship_is_centered_when_first_rendered() {
+    let mut world = world_with_actors(vec![Box::new(Ship::new(Coordinates::default()))]);
+
+    assert_snapshot("ship", &render_world(&mut world, SIZE, None));
+}
+
+#[test]
+fn ui_of_a_new_game() {
+    let mut world = started_world(HighScores::default());
+
+    assert_snapshot("ui", &render_world(&mut world, (80, 16), None));
+}
+
+#[test]
+fn ui_is_condensed_in_a_narrow_terminal() {
+    let mut world = started_world(HighScores::default());
+
+    assert_snapshot("ui_condensed", &render_world(&mut world, SIZE, None));
+}
+
+#[test]
+fn boss_health_bar() {
+    let mut world = started_world(HighScores::default());
+    world.broadcast_commands(&[Command::UpdateBossHealth(20, 30)]);
+
+    assert_snapshot("boss_health_bar", &render_world(&mut world, SIZE, None));
+}
+
+#[test]
+fn game_over_alert() {
+    let mut world = started_world(full_high_scores());
+    world.broadcast_commands(&[Command::GameOver]);
+
+    assert_snapshot("game_over_alert", &render_world(&mut world, SIZE, None));
+}
+
+#[test]
+fn menu_is_drawn_over_the_actors() {
+    let mut world = world_with_actors(Vec::new());
+    let menu = Menu::new(TITLE, vec![MenuItem::Play, MenuItem::Quit])
+        .with_body(vec![String::from("Cannot continue")]);
+
+    assert_snapshot("menu", &render_world(&mut world, SIZE, Some(&menu)));
+}
+
+// The World is 200 columns wide, so actors that cross its right edge are continued at its left edge.
+#[test]
+fn actors_wrap_around_the_edge_of_the_world() {
+    let mut world = world_with_actors(vec![
+        Box::new(Asteroid::new_large(Coordinates::new(190, 2))),
+        Box::new(Asteroid::new_small(Coordinates::new(196, 7))),
+        Box::new(Bullet::new(Coordinates::new(199, 5))),
+    ]);
+    world.offset = Coordinates::new(3, 0);
+
+    assert_snapshot("wrap_around", &render_world(&mut world, SIZE, None));
+}
+
+#[test]
+fn resize_warning_is_shown_when_the_terminal_is_too_small() {
+    let mut world = started_world(HighScores::default());
+
+    assert_snapshot("resize_warning", &render_world(&mut world, (18, 9), None));
+}
@@ -0,0 +1,369 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+// Lines of this text separate the frames of a sprite.
+static FRAME_SEPARATOR: &str = "---";
+const WATCH_PERIOD: Duration = Duration::from_millis(500);
+
+// The built-in sprites are compiled into the binary, but can be replaced by passing `--sprites <DIR>`.
+static BUILTIN_SPRITES: [(Sprite, &str, &str); 16] = [
+    (
+        Sprite::AsteroidLarge,
+        "asteroid_large.txt",
+        include_str!("../../sprites/asteroid_large.txt"),
+    ),
+    (
+        Sprite::AsteroidMedium,
+        "asteroid_medium.txt",
+        include_str!("../../sprites/asteroid_medium.txt"),
+    ),
+    (
+        Sprite::AsteroidSmall,
+        "asteroid_small.txt",
+        include_str!("../../sprites/asteroid_small.txt"),
+    ),
+    (
+        Sprite::BossCore,
+        "boss_core.txt",
+        include_str!("../../sprites/boss_core.txt"),
+    ),
+    (
+        Sprite::BossLeftWing,
+        "boss_left_wing.txt",
+        include_str!("../../sprites/boss_left_wing.txt"),
+    ),
+    (
+        Sprite::BossRightWing,
+        "boss_right_wing.txt",
+        include_str!("../../sprites/boss_right_wing.txt"),
+    ),
+    (
+        Sprite::Bullet,
+        "bullet.txt",
+        include_str!("../../sprites/bullet.txt"),
+    ),
+    (
+        Sprite::EnemyBullet,
+        "enemy_bullet.txt",
+        include_str!("../../sprites/enemy_bullet.txt"),
+    ),
+    (
+        Sprite::EnemyFighter,
+        "enemy_fighter.txt",
+        include_str!("../../sprites/enemy_fighter.txt"),
+    ),
+    (
+        Sprite::EnemyStrafer,
+        "enemy_strafer.txt",
+        include_str!("../../sprites/enemy_strafer.txt"),
+    ),
+    (
+        Sprite::Explosion,
+        "explosion.txt",
+        include_str!("../../sprites/explosion.txt"),
+    ),
+    (
+        Sprite::Missile,
+        "missile.txt",
+        include_str!("../../sprites/missile.txt"),
+    ),
+    (
+        Sprite::PowerUpHealth,
+        "power_up_health.txt",
+        include_str!("../../sprites/power_up_health.txt"),
+    ),
+    (
+        Sprite::PowerUpMissile,
+        "power_up_missile.txt",
+        include_str!("../../sprites/power_up_missile.txt"),
+    ),
+    (
+        Sprite::Ship,
+        "ship.txt",
+        include_str!("../../sprites/ship.txt"),
+    ),
+    (
+        Sprite::ShipShields,
+        "ship_shields.txt",
+        include_str!("../../sprites/ship_shields.txt"),
+    ),
+];
+
+thread_local! {
+    // Rendering only happens on the main thread.
+    static SPRITES: RefCell<BTreeMap<Sprite, Frames>> = RefCell::new(builtin_sprites());
+}
+
+// Frames are `'static`, so that actors can keep borrowing them after they're reloaded. Sprites that
+// are loaded from files are leaked, which is only repeated when they're edited during development.
+pub type Frames = Vec<&'static str>;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum Sprite {
+    AsteroidLarge,
+    AsteroidMedium,
+    AsteroidSmall,
+    BossCore,
+    BossLeftWing,
+    BossRightWing,
+    Bullet,
+    EnemyBullet,
+    EnemyFighter,
+    EnemyStrafer,
+    Explosion,
+    Missile,
+    PowerUpHealth,
+    PowerUpMissile,
+    Ship,
+    ShipShields,
+}
+
+impl Sprite {
+    // The number of frames that the sprite's file must contain, which its animation can refer to.
+    pub fn frames(self) -> usize {
+        match self {
+            Self::AsteroidLarge | Self::AsteroidMedium | Self::AsteroidSmall => 4,
+            Self::Explosion => 3,
+            Self::Ship => 2,
+            _ => 1,
+        }
+    }
+
+    fn from_file_name(file_name: &str) -> Option<Self> {
+        BUILTIN_SPRITES
+            .iter()
+            .find(|(_, other, _)| *other == file_name)
+            .map(|(sprite, _, _)| *sprite)
+    }
+}
+
+// The first frame of a sprite.
+pub fn text(sprite: Sprite) -> &'static str {
+    frame(sprite, 0)
+}
+
+pub fn frame(sprite: Sprite, index: usize) -> &'static str {
+    SPRITES.with(|sprites| sprites.borrow()[&sprite][index])
+}
+
+// Replace the built-in sprites with the `*.txt` files in a directory, which needn't contain all of
+// them. Nothing is replaced unless every file is valid.
+pub fn load_sprites(directory: &Path) -> Result<()> {
+    let entries = fs::read_dir(directory)
+        .with_context(|| format!("cannot read sprites directory: {}", directory.display()))?;
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if is_sprite_file(&path) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    let sprites = paths
+        .iter()
+        .map(|path| load_sprite(path))
+        .collect::<Result<Vec<_>>>()?;
+    for (sprite, frames) in sprites {
+        install(sprite, frames);
+    }
+    Ok(())
+}
+
+// Reload the sprites in a directory whenever their files change, so that they can be edited while
+// the game is running. Files are parsed on another thread, and then sent to be `install()`ed.
+pub fn watch_sprites(directory: PathBuf) -> Receiver<Result<(Sprite, Frames)>> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut modified = modification_times(&directory);
+        loop {
+            thread::sleep(WATCH_PERIOD);
+            let current = modification_times(&directory);
+            for (path, time) in &current {
+                if modified.get(path) != Some(time) && tx.send(load_sprite(path)).is_err() {
+                    return; // The game has ended
+                }
+            }
+            modified = current;
+        }
+    });
+    rx
+}
+
+pub fn install(sprite: Sprite, frames: Frames) {
+    SPRITES.with(|sprites| sprites.borrow_mut().insert(sprite, frames));
+}
+
+fn builtin_sprites() -> BTreeMap<Sprite, Frames> {
+    BUILTIN_SPRITES
+        .iter()
+        .map(|(sprite, file_name, text)| {
+            let frames = parse(text, sprite.frames())
+                .with_context(|| format!("invalid sprite file: {file_name}"))
+                .expect("Built-in sprites are valid");
+            (*sprite, frames)
+        })
+        .collect()
+}
+
+fn is_sprite_file(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "txt")
+}
+
+fn load_sprite(path: &Path) -> Result<(Sprite, Frames)> {
+    let sprite = path
+        .file_name()
+        .and_then(|file_name| Sprite::from_file_name(&file_name.to_string_lossy()))
+        .ok_or_else(|| anyhow!("unknown sprite file: {}", path.display()))?;
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("cannot read sprite file: {}", path.display()))?;
+    let frames = parse(text.leak(), sprite.frames())
+        .with_context(|| format!("invalid sprite file: {}", path.display()))?;
+    Ok((sprite, frames))
+}
+
+// Files that can't be read are skipped, so that they're reloaded once they can be.
+fn modification_times(directory: &Path) -> BTreeMap<PathBuf, SystemTime> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return BTreeMap::new();
+    };
+    entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let modified = fs::metadata(&path).ok()?.modified().ok()?;
+            is_sprite_file(&path).then_some((path, modified))
+        })
+        .collect()
+}
+
+// Each frame must be a non-empty rectangle, because spaces are drawn over whatever is behind them,
+// and trailing spaces that were trimmed by an editor would otherwise change the sprite's shape.
+fn parse(text: &'static str, expected_frames: usize) -> Result<Frames> {
+    let mut frames = Vec::new();
+    let mut frame: Option<(usize, usize, usize)> = None; // The offset, number, and width of its first line
+    let mut end = 0;
+    let mut offset = 0;
+    for (index, line) in text.split_inclusive('\n').enumerate() {
+        let number = index + 1;
+        let start = offset;
+        offset += line.len();
+        let line = line.strip_suffix('\n').unwrap_or(line);
+        if line == FRAME_SEPARATOR {
+            let (frame_start, _, _) =
+                frame.ok_or_else(|| anyhow!("line {number} follows an empty frame"))?;
+            frames.push(&text[frame_start..end]);
+            frame = None;
+            continue;
+        }
+        let width = line.chars().count();
+        if line.contains('\r') {
+            return Err(anyhow!(
+                "line {number} must end with \\n rather than \\r\\n"
+            ));
+        }
+        if width > usize::from(u8::MAX) {
+            return Err(anyhow!(
+                "line {number} is wider than {} characters",
+                u8::MAX
+            ));
+        }
+        match frame {
+            None if width == 0 => return Err(anyhow!("line {number} is empty")),
+            None => frame = Some((start, number, width)),
+            Some((_, first_number, first_width)) if width != first_width => {
+                return Err(anyhow!(
+                    "line {number} is {width} characters wide, but line {first_number} is {first_width}"
+                ));
+            }
+            Some(_) => (),
+        }
+        end = start + line.len();
+    }
+    let (frame_start, _, _) = frame.ok_or_else(|| anyhow!("the last frame is empty"))?;
+    frames.push(&text[frame_start..end]);
+    if frames.len() != expected_frames {
+        return Err(anyhow!(
+            "expected {expected_frames} frame(s), but found {}",
+            frames.len()
+        ));
+    }
+    Ok(frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn builtin_sprites_are_valid() {
+        assert_eq!(builtin_sprites().len(), BUILTIN_SPRITES.len());
+    }
+
+    #[test]
+    fn parse_splits_frames() {
+        let frames = parse("ab\ncd\n---\ne \n f\n", 2).unwrap();
+
+        assert_eq!(frames, ["ab\ncd", "e \n f"]);
+    }
+
+    #[test]
+    fn parse_reports_the_line_of_an_inconsistent_width() {
+        let message = parse("abc\nabc\n---\nab\na\n", 2).unwrap_err().to_string();
+
+        assert_eq!(message, "line 5 is 1 characters wide, but line 4 is 2");
+    }
+
+    #[test]
+    fn parse_rejects_empty_frames() {
+        assert_eq!(
+            parse("---\nab\n", 1).unwrap_err().to_string(),
+            "line 1 follows an empty frame"
+        );
+        assert_eq!(
+            parse("ab\n---\n", 2).unwrap_err().to_string(),
+            "the last frame is empty"
+        );
+        assert_eq!(
+            parse("\nab\n", 1).unwrap_err().to_string(),
+            "line 1 is empty"
+        );
+    }
+
+    #[test]
+    fn parse_checks_the_number_of_frames() {
+        assert_eq!(
+            parse("ab\n", 4).unwrap_err().to_string(),
+            "expected 4 frame(s), but found 1"
+        );
+    }
+
+    #[test]
+    fn load_sprites_names_the_invalid_file() {
+        let directory =
+            env::temp_dir().join(format!("space_time_rewind_sprites_{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("ship.txt");
+        fs::write(&path, "◄◆►\n◄◆\n").unwrap();
+
+        let result = load_sprites(&directory);
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(
+            format!("{:#}", result.unwrap_err()),
+            format!(
+                "invalid sprite file: {}: line 2 is 2 characters wide, but line 1 is 3",
+                path.display()
+            )
+        );
+        assert_eq!(text(Sprite::Ship), "◄◆►"); // The built-in sprite is kept
+    }
+}
@@ -0,0 +1,88 @@
+use super::coordinates::{Coordinates, Movement};
+use crate::clock::ticker::Frequency;
+use serde::{Deserialize, Serialize};
+
+// Velocities are measured in 1/64ths of a cell per tick, so that actors can move slower than once
+// per tick, and in directions other than straight up, down, left, or right.
+pub const SUBCELLS: i16 = 64;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Velocity {
+    dx: i16,
+    dy: i16,
+    remainder: Movement, // Subcells that have accumulated, but don't yet amount to a whole cell
+}
+
+impl Velocity {
+    pub fn new(dx: i16, dy: i16) -> Self {
+        Self {
+            dx,
+            dy,
+            remainder: (0, 0),
+        }
+    }
+
+    // The equivalent of moving by `movement` whenever `ticker.at(frequency)`.
+    pub fn from_frequency((dx, dy): Movement, frequency: Frequency) -> Self {
+        let ticks = i16::try_from(u16::from(frequency)).unwrap();
+        assert!(ticks <= SUBCELLS);
+        Self::new(dx * SUBCELLS / ticks, dy * SUBCELLS / ticks)
+    }
+
+    // Change the horizontal velocity, such as when steering towards a target.
+    pub fn steer(&mut self, dx: i16) {
+        self.dx = dx;
+    }
+
+    // Advance by one tick, and move the coordinates by the whole cells that have accumulated.
+    pub fn move_coordinates(&mut self, coordinates: &mut Coordinates) {
+        let (dx, dy) = self.step();
+        if dx != 0 {
+            coordinates.offset_x(dx);
+        }
+        if dy != 0 {
+            coordinates.offset_y(dy);
+        }
+    }
+
+    fn step(&mut self) -> Movement {
+        let (x, y) = self.remainder;
+        let x = x + self.dx;
+        let y = y + self.dy;
+        // Truncate towards zero, so that moving left and down are symmetric with right and up.
+        self.remainder = (x % SUBCELLS, y % SUBCELLS);
+        (x / SUBCELLS, y / SUBCELLS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accumulates_fractional_movement() {
+        let mut velocity = Velocity::new(SUBCELLS / 2, -SUBCELLS / 4);
+        let steps: Vec<Movement> = (0..4).map(|_| velocity.step()).collect();
+
+        assert_eq!(steps, vec![(0, 0), (1, 0), (0, 0), (1, -1)]);
+    }
+
+    #[test]
+    fn from_frequency_moves_once_per_frequency() {
+        let mut velocity = Velocity::from_frequency((0, -1), Frequency::Four);
+        let cells: i16 = (0..u16::from(Frequency::Four) * 3)
+            .map(|_| velocity.step().1)
+            .sum();
+
+        assert_eq!(cells, -3);
+    }
+
+    #[test]
+    fn move_coordinates_wraps_x() {
+        let mut coordinates = Coordinates::new(199, 5);
+        let mut velocity = Velocity::new(SUBCELLS, SUBCELLS);
+        velocity.move_coordinates(&mut coordinates);
+
+        assert_eq!(coordinates.as_tuple(), (0, 6));
+    }
+}
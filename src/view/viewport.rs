@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::cmp::{max, min};
 
 use super::{
@@ -5,7 +6,7 @@ use super::{
     factory::{WORLD_HEIGHT, WORLD_WIDTH},
 };
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Viewport {
     bottom_left: Coordinates,
     width: u8,
@@ -78,6 +79,21 @@ impl Viewport {
         Coordinates::new(x, y)
     }
 
+    // The signed horizontal distance from the center of `self` to the center of `other`,
+    // which is the shortest distance in either direction, because the world wraps around.
+    pub fn distance_x(&self, other: Viewport) -> i16 {
+        let world_width = i16::from(WORLD_WIDTH);
+        let (x1, _) = self.centered().as_tuple();
+        let (x2, _) = other.centered().as_tuple();
+        let mut dx = i16::from(x2) - i16::from(x1);
+        if dx > world_width / 2 {
+            dx -= world_width;
+        } else if dx < -world_width / 2 {
+            dx += world_width;
+        }
+        dx
+    }
+
     pub fn expanded(&self, wider_width: u8, taller_height: u8) -> Coordinates {
         let (x, y) = self.bottom_left().as_tuple();
         let x_offset = wider_width.saturating_sub(self.width) / 2;
@@ -86,6 +102,10 @@ impl Viewport {
         Coordinates::new(x - x_offset, y - y_offset)
     }
 
+    pub fn height(&self) -> u8 {
+        self.height
+    }
+
     pub fn intersects(&self, other: Self) -> bool {
         self.intersects_horizontally(other) && self.intersects_vertically(other)
     }
@@ -94,18 +114,14 @@ impl Viewport {
         let world_width = i16::from(WORLD_WIDTH);
         let (x1, _) = self.bottom_left().as_tuple();
         let (x2, _) = other.bottom_left().as_tuple();
-        let mut x1 = i16::from(x1);
-        let mut x2 = i16::from(x2);
+        let x1 = i16::from(x1);
+        let x2 = i16::from(x2);
         let w1 = i16::from(self.width);
         let w2 = i16::from(other.width);
-        // Account for x-wrapping
-        if x1 + w1 > world_width {
-            x1 -= world_width;
-        }
-        if x2 + w2 > world_width {
-            x2 -= world_width;
-        }
-        intersects(x1, x2, w1, w2)
+        // Account for x-wrapping: either viewport may extend past the right edge of the world.
+        [-world_width, 0, world_width]
+            .iter()
+            .any(|offset| intersects(x1 + offset, x2, w1, w2))
     }
 
     pub fn intersects_vertically(&self, other: Self) -> bool {
@@ -217,6 +233,36 @@ mod tests {
         assert_eq!(y, 1);
     }
 
+    #[test]
+    fn distance_x_returns_signed_distance() {
+        let l = Viewport::new_with_coordinates(1, 1, Coordinates::new(10, 0));
+        let r = Viewport::new_with_coordinates(1, 1, Coordinates::new(15, 0));
+
+        assert_eq!(l.distance_x(r), 5);
+        assert_eq!(r.distance_x(l), -5);
+    }
+
+    #[test]
+    fn distance_x_handles_x_wrapping() {
+        let l = Viewport::new_with_coordinates(1, 1, Coordinates::new(2, 0));
+        let r = Viewport::new_with_coordinates(1, 1, Coordinates::new(198, 0));
+
+        assert_eq!(l.distance_x(r), -4);
+        assert_eq!(r.distance_x(l), 4);
+    }
+
+    #[test]
+    fn intersects_handles_x_wrapping() {
+        let wrapped = Viewport::new_with_coordinates(10, 1, Coordinates::new(195, 0));
+        let left = Viewport::new_with_coordinates(2, 1, Coordinates::new(196, 0));
+        let right = Viewport::new_with_coordinates(2, 1, Coordinates::new(3, 0));
+
+        assert!(wrapped.intersects(left));
+        assert!(wrapped.intersects(right));
+        assert!(left.intersects(wrapped));
+        assert!(right.intersects(wrapped));
+    }
+
     #[test]
     fn intersects_returns_true_when_overlapping() {
         let bl = Viewport::new_with_coordinates(2, 2, Coordinates::new(0, 0));